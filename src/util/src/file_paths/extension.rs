@@ -0,0 +1,147 @@
+/// Splits the base name of a path into the portion before its last
+/// extension and the extension without the dot. Returns `None` for
+/// a base name without an extension or a dotfile such as `.bashrc`.
+fn split_extension(path: &str) -> Option<(&str, &str)> {
+    let base_name = path.rsplit(['/', '\\']).next().unwrap_or("");
+    let (stem, extension) = base_name.rsplit_once('.')?;
+    if stem.is_empty() || extension.is_empty() {
+        return None;
+    }
+    Some((&path[..path.len() - extension.len() - 1], extension))
+}
+
+/// Returns the canonical form of an extension given with or without its
+/// leading dot, for comparing extensions: a single leading dot is removed
+/// and the extension is lowercased, so both `.TXT` and `txt` become `txt`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths;
+/// assert_eq!(file_paths::canonical_extension(".TXT"), file_paths::canonical_extension("txt"));
+/// ```
+pub fn canonical_extension(ext: &str) -> String {
+    ext.strip_prefix('.').unwrap_or(ext).to_lowercase()
+}
+
+/// Lowercases the last extension of a path, leaving the rest of the
+/// path, including the casing of the stem, intact. A path without an
+/// extension or whose base name is a dotfile is returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths;
+/// assert_eq!("a/Photo.jpg", file_paths::normalize_extension("a/Photo.JPG"));
+/// assert_eq!(".Bashrc", file_paths::normalize_extension(".Bashrc"));
+/// ```
+pub fn normalize_extension(path: &str) -> String {
+    match split_extension(path) {
+        Some((rest, extension)) => format!("{}.{}", rest, canonical_extension(extension)),
+        None => path.to_owned(),
+    }
+}
+
+/// Returns the trailing extensions of a path without the leading dot,
+/// from longest to shortest, so that a type detection can try the most
+/// specific first. For `a.tar.gz`, it returns `["tar.gz", "gz"]`.
+///
+/// The stem of the base name is never part of an extension, therefore
+/// a name without an extension or a dotfile such as `.bashrc` produces
+/// no candidates.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths;
+/// assert_eq!(vec!["tar.gz", "gz"], file_paths::extension_candidates("dist/a.tar.gz"));
+/// ```
+pub fn extension_candidates(path: &str) -> Vec<String> {
+    let base_name = path.rsplit(['/', '\\']).next().unwrap_or("");
+    let base_name = base_name.strip_prefix('.').unwrap_or(base_name);
+    let parts: Vec<&str> = base_name.split('.').skip(1).collect();
+    let first = parts.iter().rposition(|p| p.is_empty()).map_or(0, |i| i + 1);
+    (first..parts.len()).map(|i| parts[i..].join(".")).collect()
+}
+
+/// Returns `name` if `exists` returns `false` for it, otherwise the first
+/// name with an incrementing ` (n)` suffix, starting at 1, for which
+/// `exists` returns `false`, such as `report (1).txt` for `report.txt`.
+///
+/// The suffix is inserted before the last extension, or before both
+/// extensions of a compound extension such as `.tar.gz`. A name without
+/// an extension or a dotfile gets the suffix at its end.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths;
+/// let taken = ["report.txt", "report (1).txt"];
+/// assert_eq!("report (2).txt", file_paths::uniquify("report.txt", &|name| taken.contains(&name)));
+/// ```
+pub fn uniquify(name: &str, exists: &dyn Fn(&str) -> bool) -> String {
+    if !exists(name) {
+        return name.to_owned();
+    }
+    let (stem, extension) = match split_extension(name) {
+        Some((rest, _)) => match split_extension(rest) {
+            Some((inner, tar)) if tar.eq_ignore_ascii_case("tar") => (inner, &name[inner.len()..]),
+            _ => (rest, &name[rest.len()..]),
+        },
+        None => (name, ""),
+    };
+    (1usize..).map(|n| format!("{} ({}){}", stem, n, extension)).find(|candidate| !exists(candidate)).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn candidates() {
+        assert_eq!(vec!["gz"], extension_candidates("a.gz"));
+        assert_eq!(vec!["tar.gz", "gz"], extension_candidates("a.tar.gz"));
+        assert_eq!(vec!["d.ts", "ts"], extension_candidates(r"x.y\index.d.ts"));
+        assert!(extension_candidates("Makefile").is_empty());
+        assert!(extension_candidates("dir.x/Makefile").is_empty());
+        assert!(extension_candidates(".bashrc").is_empty());
+        assert_eq!(vec!["bak"], extension_candidates(".bashrc.bak"));
+        assert!(extension_candidates("a.").is_empty());
+        assert_eq!(vec!["gz"], extension_candidates("a..gz"));
+    }
+
+    #[test]
+    fn unique_names() {
+        let taken = ["report.txt", "report (1).txt", "report (2).txt", "a.tar.gz", "Makefile", ".env", "x.y.z"];
+        let exists = |name: &str| taken.contains(&name);
+        assert_eq!("report (3).txt", uniquify("report.txt", &exists));
+        assert_eq!("new.txt", uniquify("new.txt", &exists));
+        assert_eq!("a (1).tar.gz", uniquify("a.tar.gz", &exists));
+        assert_eq!("Makefile (1)", uniquify("Makefile", &exists));
+        assert_eq!(".env (1)", uniquify(".env", &exists));
+        assert_eq!("x.y (1).z", uniquify("x.y.z", &exists));
+        assert_eq!("dir.d/report (1).txt", uniquify("dir.d/report.txt", &|name| name == "dir.d/report.txt"));
+    }
+
+    #[test]
+    fn canonical() {
+        assert_eq!("txt", canonical_extension(".TXT"));
+        assert_eq!("txt", canonical_extension("txt"));
+        assert_eq!("tar.gz", canonical_extension(".Tar.GZ"));
+        assert_eq!(".txt", canonical_extension("..txt"));
+        assert_eq!("", canonical_extension(""));
+        assert_eq!("", canonical_extension("."));
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!("Photo.jpg", normalize_extension("Photo.JPG"));
+        assert_eq!("A.Tar.gz", normalize_extension("A.Tar.Gz"));
+        assert_eq!(r"Dir.X\File.png", normalize_extension(r"Dir.X\File.pNg"));
+        assert_eq!("Makefile", normalize_extension("Makefile"));
+        assert_eq!("Dir.X/Makefile", normalize_extension("Dir.X/Makefile"));
+        assert_eq!(".GitIgnore", normalize_extension(".GitIgnore"));
+        assert_eq!("a/.Env.local", normalize_extension("a/.Env.LOCAL"));
+        assert_eq!("Name.", normalize_extension("Name."));
+    }
+}
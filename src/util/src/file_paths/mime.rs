@@ -0,0 +1,82 @@
+/// Common file extensions and their MIME types.
+static MIME_TYPES: [(&str, &str); 38] = [
+    ("aac", "audio/aac"),
+    ("avif", "image/avif"),
+    ("bmp", "image/bmp"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mjs", "text/javascript"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("mpeg", "video/mpeg"),
+    ("oga", "audio/ogg"),
+    ("ogg", "audio/ogg"),
+    ("ogv", "video/ogg"),
+    ("otf", "font/otf"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("toml", "application/toml"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("wav", "audio/wav"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("xml", "application/xml"),
+    ("zip", "application/zip"),
+];
+
+/// Guesses the MIME type of a file path from its last extension,
+/// matching it case-insensitively against a table of common extensions.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths;
+/// assert_eq!(Some("text/html"), file_paths::guess_mime_type("www/index.HTML"));
+/// assert_eq!(None, file_paths::guess_mime_type("www/index"));
+/// ```
+pub fn guess_mime_type(path: &str) -> Option<&'static str> {
+    let base_name = path.rsplit(['/', '\\']).next().unwrap_or("");
+    let (stem, extension) = base_name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    let extension = super::canonical_extension(extension);
+    MIME_TYPES.iter().find(|(e, _)| *e == extension).map(|(_, mime)| *mime)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mime_types() {
+        assert_eq!(Some("text/html"), guess_mime_type("index.html"));
+        assert_eq!(Some("text/css"), guess_mime_type("a/b/style.CSS"));
+        assert_eq!(Some("text/javascript"), guess_mime_type(r"a\app.js"));
+        assert_eq!(Some("application/json"), guess_mime_type("data.json"));
+        assert_eq!(Some("image/png"), guess_mime_type("icon.Png"));
+        assert_eq!(Some("image/svg+xml"), guess_mime_type("logo.svg"));
+        assert_eq!(Some("application/wasm"), guess_mime_type("app_bg.wasm"));
+        assert_eq!(Some("application/gzip"), guess_mime_type("archive.tar.gz"));
+        assert_eq!(None, guess_mime_type("file.unknown"));
+        assert_eq!(None, guess_mime_type("Makefile"));
+        assert_eq!(None, guess_mime_type(".json"));
+        assert_eq!(None, guess_mime_type("a.js/b"));
+    }
+}
@@ -0,0 +1,34 @@
+/*!
+Work with file paths by text only.
+
+This module re-exports the [`Path`] structure, which represents an
+always-resolved path based on a [`PlatformPathVariant`].
+
+The [`os_based`] submodule provides functions that manipulate path strings
+directly, taking the manipulation as an argument, and the [`url`]
+submodule works with the path component of URLs.
+
+# Example
+
+```
+use rialight_util::file_paths::Path;
+
+assert_eq!("a", Path::new_common("a/b").resolve("..").to_string());
+assert_eq!("../../c/d", Path::new_common("/a/b").relative("/c/d"));
+```
+*/
+
+pub use ::file_paths::*;
+
+pub mod os_based;
+
+mod separators;
+pub use separators::{normalize_with_separator, resolve_with_separator, separator_style, SeparatorStyle};
+
+mod mime;
+pub use mime::guess_mime_type;
+
+mod extension;
+pub use extension::{canonical_extension, extension_candidates, normalize_extension, uniquify};
+
+pub mod url;
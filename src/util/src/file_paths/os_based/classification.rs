@@ -0,0 +1,119 @@
+use super::*;
+
+/// The kind of a Windows path, as returned by [`windows_path_kind`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WindowsPathKind {
+    /// A path starting with a drive letter and a root, such as `C:\x`.
+    Drive,
+    /// An UNC path, such as `\\server\share`.
+    Unc,
+    /// A path in the device namespace, such as `\\.\PhysicalDrive0`.
+    DeviceNamespace,
+    /// An extended-length path, such as `\\?\C:\x`.
+    Extended,
+    /// A path starting with a drive letter but no root, such as `C:x`,
+    /// which is relative to the current directory of that drive.
+    DriveRelative,
+    /// A relative path, such as `x\y`.
+    Relative,
+    /// A path starting with a root but no drive letter, such as `\x`.
+    RootedNoDrive,
+}
+
+/// Classifies a path according to the Windows operating system.
+/// Both forward slashes and backslashes are taken as path separators.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, WindowsPathKind};
+/// assert_eq!(WindowsPathKind::Unc, os_based::windows_path_kind(r"\\server\share"));
+/// assert_eq!(WindowsPathKind::Extended, os_based::windows_path_kind(r"\\?\C:\x"));
+/// ```
+pub fn windows_path_kind(path: &str) -> WindowsPathKind {
+    if reg_exp_is_match!(r"^[/\\]{2}\?[/\\]", path) {
+        WindowsPathKind::Extended
+    } else if reg_exp_is_match!(r"^[/\\]{2}\.[/\\]", path) {
+        WindowsPathKind::DeviceNamespace
+    } else if reg_exp_is_match!(r"^[/\\]{2}", path) {
+        WindowsPathKind::Unc
+    } else if reg_exp_is_match!(r"^[A-Za-z]:[/\\]", path) {
+        WindowsPathKind::Drive
+    } else if reg_exp_is_match!(r"^[A-Za-z]:", path) {
+        WindowsPathKind::DriveRelative
+    } else if reg_exp_is_match!(r"^[/\\]", path) {
+        WindowsPathKind::RootedNoDrive
+    } else {
+        WindowsPathKind::Relative
+    }
+}
+
+/// Returns `path` unchanged if it is absolute, and otherwise resolves it
+/// against `base` as [`resolve`] does.
+///
+/// For the `Windows` manipulation, a drive-relative path such as `C:x`
+/// is not absolute: it is resolved against `base` if `base` is on the
+/// same drive, and otherwise against the root of its drive.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let windows = OsPathManipulation::Windows;
+/// assert_eq!(r"D:\x\..\y", os_based::ensure_absolute("C:/base", r"D:\x\..\y", windows));
+/// assert_eq!("C:/base/y", os_based::ensure_absolute("C:/base", "x/../y", windows));
+/// assert_eq!("C:/base/y", os_based::ensure_absolute("C:/base", "c:y", windows));
+/// ```
+pub fn ensure_absolute(base: &str, path: &str, manipulation: OsPathManipulation) -> String {
+    if manipulation == OsPathManipulation::Windows && windows_path_kind(path) == WindowsPathKind::DriveRelative {
+        let (drive, rest) = path.split_at(2);
+        let base = if base.get(..2).is_some_and(|d| d.eq_ignore_ascii_case(drive)) && is_absolute(base, manipulation) {
+            base.to_owned()
+        } else {
+            drive.to_owned() + "/"
+        };
+        return resolve(&base, rest, manipulation);
+    }
+    if is_absolute(path, manipulation) {
+        return path.to_owned();
+    }
+    resolve(base, path, manipulation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ensuring_absolute() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(r"D:\a\.\b", ensure_absolute("C:/base", r"D:\a\.\b", windows));
+        assert_eq!(r"\\srv\share", ensure_absolute("C:/base", r"\\srv\share", windows));
+        assert_eq!("C:/base/x", ensure_absolute(r"C:\base", r"x\", windows));
+        assert_eq!("C:/x", ensure_absolute("C:/base", "../x", windows));
+        assert_eq!("C:/base/x", ensure_absolute("C:/base", "C:x", windows));
+        assert_eq!("C:/x", ensure_absolute("C:/base", "C:../x", windows));
+        assert_eq!("d:/x", ensure_absolute("C:/base", "d:x", windows));
+        assert_eq!("D:/", ensure_absolute("C:/base", "D:", windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!("/a/./b", ensure_absolute("/base", "/a/./b", common));
+        assert_eq!("/base/a/b", ensure_absolute("/base", "a/b", common));
+        assert_eq!("/base/C:x", ensure_absolute("/base", "C:x", common));
+    }
+
+    #[test]
+    fn kinds() {
+        assert_eq!(WindowsPathKind::Drive, windows_path_kind(r"C:\x"));
+        assert_eq!(WindowsPathKind::Drive, windows_path_kind("c:/"));
+        assert_eq!(WindowsPathKind::Unc, windows_path_kind(r"\\server\share"));
+        assert_eq!(WindowsPathKind::Unc, windows_path_kind("//server/share"));
+        assert_eq!(WindowsPathKind::DeviceNamespace, windows_path_kind(r"\\.\PhysicalDrive0"));
+        assert_eq!(WindowsPathKind::Extended, windows_path_kind(r"\\?\C:\x"));
+        assert_eq!(WindowsPathKind::DriveRelative, windows_path_kind("C:x"));
+        assert_eq!(WindowsPathKind::DriveRelative, windows_path_kind("C:"));
+        assert_eq!(WindowsPathKind::Relative, windows_path_kind(r"x\y"));
+        assert_eq!(WindowsPathKind::Relative, windows_path_kind(""));
+        assert_eq!(WindowsPathKind::RootedNoDrive, windows_path_kind(r"\x"));
+    }
+}
@@ -0,0 +1,100 @@
+use super::*;
+
+/// The `DecodeError` enumeration represents an error that occurred
+/// while percent-decoding the segments of a path.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+    /// A `%` is not followed by two hexadecimal digits.
+    InvalidEscape {
+        segment: String,
+    },
+    /// The decoded bytes of a segment are not valid UTF-8.
+    InvalidUtf8 {
+        segment: String,
+    },
+    /// A segment decodes to a path separator, a null character,
+    /// `.` or `..`, which would change the structure of the path.
+    UnsafeSegment {
+        segment: String,
+    },
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidEscape { segment } => write!(f, "Invalid percent escape in segment {:?}", segment),
+            Self::InvalidUtf8 { segment } => write!(f, "Segment {:?} does not decode to valid UTF-8", segment),
+            Self::UnsafeSegment { segment } => write!(f, "Segment {:?} decodes to an unsafe path portion", segment),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Resolves `path2` relative to `path1` as [`resolve`] does, then
+/// percent-decodes each segment, such as `foo%20bar` into `foo bar`.
+/// The prefix of an absolute path is not decoded.
+///
+/// Fails if a segment contains an invalid escape, does not decode to
+/// valid UTF-8, or decodes to something that would change the structure
+/// of the path, such as `%2F` or `%2E%2E`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(Ok("a/foo bar".to_owned()), os_based::resolve_decoding("a", "foo%20bar", OsPathManipulation::Common));
+/// ```
+pub fn resolve_decoding(path1: &str, path2: &str, manipulation: OsPathManipulation) -> Result<String, DecodeError> {
+    let (prefix, segments) = split_prefix(&resolve(path1, path2, manipulation), manipulation);
+    let segments = segments.iter().map(|s| decode_segment(s, manipulation)).collect::<Result<Vec<String>, DecodeError>>()?;
+    Ok(prefix + &segments.join("/"))
+}
+
+fn decode_segment(segment: &str, manipulation: OsPathManipulation) -> Result<String, DecodeError> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::<u8>::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3).filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()));
+            let Some(hex) = hex else {
+                return Err(DecodeError::InvalidEscape { segment: segment.to_owned() });
+            };
+            decoded.push(u8::from_str_radix(hex, 16).unwrap());
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    let decoded = String::from_utf8(decoded).map_err(|_| DecodeError::InvalidUtf8 { segment: segment.to_owned() })?;
+    let separator = |ch: char| ch == '/' || ch == '\0' || (ch == '\\' && manipulation == OsPathManipulation::Windows);
+    if decoded == "." || decoded == ".." || decoded.contains(separator) {
+        return Err(DecodeError::UnsafeSegment { segment: segment.to_owned() });
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decoding() {
+        let common = OsPathManipulation::Common;
+        assert_eq!(Ok("a/foo bar".to_owned()), resolve_decoding("a", "foo%20bar", common));
+        assert_eq!(Ok("/x/caf\u{e9}/%".to_owned()), resolve_decoding("/x/y", "../caf%C3%A9/%25", common));
+        assert_eq!(Ok(r"a/b\c".to_owned()), resolve_decoding("a", "b%5Cc", common));
+        assert_eq!(Ok("".to_owned()), resolve_decoding("a", "..", common));
+        assert_eq!(Err(DecodeError::InvalidEscape { segment: "b%2".into() }), resolve_decoding("a", "b%2", common));
+        assert_eq!(Err(DecodeError::InvalidEscape { segment: "%zz".into() }), resolve_decoding("a", "%zz", common));
+        assert_eq!(Err(DecodeError::InvalidUtf8 { segment: "%FF".into() }), resolve_decoding("a", "%FF", common));
+        assert_eq!(Err(DecodeError::UnsafeSegment { segment: "x%2Fy".into() }), resolve_decoding("a", "x%2Fy", common));
+        assert_eq!(Err(DecodeError::UnsafeSegment { segment: "%2E%2E".into() }), resolve_decoding("a", "%2E%2E", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Ok("C:/My Files/a".to_owned()), resolve_decoding(r"C:\My%20Files", "a", windows));
+        assert!(resolve_decoding("C:/", "b%5Cc", windows).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+/// Returns the last `n` portions of a resolved path joined with `/`,
+/// for breadcrumb-style displays. If portions are omitted, the result
+/// is prefixed with `…/`; otherwise the whole path, including any
+/// prefix, is returned.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("…/c/d", os_based::tail("C:/a/b/c/d", 2, OsPathManipulation::Windows));
+/// ```
+pub fn tail(path: &str, n: usize, manipulation: OsPathManipulation) -> String {
+    let (_, segments) = split_prefix(path, manipulation);
+    if n >= segments.len() {
+        return resolve_one(path, manipulation);
+    }
+    std::iter::once("…").chain(segments[segments.len() - n..].iter().map(|s| s.as_str())).collect::<Vec<_>>().join("/")
+}
+
+/// Replaces a leading `home` directory in a resolved path by `~`, as
+/// shells display paths in prompts. For the `Windows` manipulation,
+/// portions are compared case-insensitively. A path outside `home` is
+/// only resolved.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("~/projects/app", os_based::shorten_home("/home/u/projects/app", "/home/u", OsPathManipulation::Common));
+/// ```
+pub fn shorten_home(path: &str, home: &str, manipulation: OsPathManipulation) -> String {
+    let (prefix, segments) = split_prefix(path, manipulation);
+    let (home_prefix, home_segments) = split_prefix(home, manipulation);
+    let inside = !home_prefix.is_empty()
+        && portion_eq(&prefix, &home_prefix, manipulation)
+        && segments.len() >= home_segments.len()
+        && segments.iter().zip(home_segments.iter()).all(|(a, b)| portion_eq(a, b, manipulation));
+    if !inside {
+        return resolve_one(path, manipulation);
+    }
+    std::iter::once("~").chain(segments[home_segments.len()..].iter().map(|s| s.as_str())).collect::<Vec<_>>().join("/")
+}
+
+/// Shortens a string to at most `max_len` characters by replacing its
+/// middle with `…`, keeping slightly more of the end, where the file name
+/// is. A string that is short enough is returned as it is.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based;
+/// assert_eq!("/very/…ile.txt", os_based::truncate_middle("/very/long/path/to/file.txt", 14));
+/// ```
+pub fn truncate_middle(path: &str, max_len: usize) -> String {
+    let chars: Vec<char> = path.chars().collect();
+    if chars.len() <= max_len {
+        return path.to_owned();
+    }
+    if max_len == 0 {
+        return "".to_owned();
+    }
+    let head = (max_len - 1) / 2;
+    let tail = max_len - 1 - head;
+    chars[..head].iter().chain(std::iter::once(&'…')).chain(&chars[chars.len() - tail..]).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn home() {
+        let common = OsPathManipulation::Common;
+        assert_eq!("~/projects/app", shorten_home("/home/u/projects/app/", "/home/u/", common));
+        assert_eq!("~", shorten_home("/home/u", "/home/u", common));
+        assert_eq!("/home/user2/x", shorten_home("/home/user2/x", "/home/u", common));
+        assert_eq!("/Home/u", shorten_home("/Home/u", "/home/u", common));
+        assert_eq!("a/b", shorten_home("a/b", "", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("~/Documents", shorten_home(r"c:\users\ME\Documents", r"C:\Users\me", windows));
+        assert_eq!("D:/Users/me", shorten_home(r"D:\Users\me", r"C:\Users\me", windows));
+    }
+
+    #[test]
+    fn middle_truncation() {
+        let path = "/very/long/path/to/file.txt";
+        assert_eq!(path, truncate_middle(path, 27));
+        assert_eq!("/very/long/p…h/to/file.txt", truncate_middle(path, 26));
+        assert_eq!("/very/…ile.txt", truncate_middle(path, 14));
+        assert_eq!(14, truncate_middle(path, 14).chars().count());
+        assert_eq!("…t", truncate_middle(path, 2));
+        assert_eq!("…", truncate_middle(path, 1));
+        assert_eq!("", truncate_middle(path, 0));
+        assert_eq!("ä…ö", truncate_middle("äbcdö", 3));
+    }
+
+    #[test]
+    fn tails() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("…/c/d", tail("C:/a/b/c/d", 2, windows));
+        assert_eq!("…/d", tail(r"C:\a\b\c\d\", 1, windows));
+        assert_eq!("C:/a/b/c/d", tail("C:/a/b/c/d", 4, windows));
+        assert_eq!("C:/a/b/c/d", tail("C:/a/b/c/d", 10, windows));
+        assert_eq!("…", tail("C:/a", 0, windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!("…/b/c", tail("a/b/c", 2, common));
+        assert_eq!("a/b/c", tail("a/b/c", 3, common));
+        assert_eq!("/", tail("/", 2, common));
+    }
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+/// Walks from `start` up through its [`ancestors`], returning the first
+/// directory in which `marker` exists on disk. `start` itself is
+/// checked first.
+///
+/// This function is only available with the `std-fs` feature.
+pub fn find_up(start: &str, marker: &str, manipulation: OsPathManipulation) -> Option<String> {
+    let start = resolve_one(start, manipulation);
+    std::iter::once(start.clone())
+        .chain(ancestors(&start, manipulation))
+        .find(|dir| std::path::Path::new(&resolve(dir, marker, manipulation)).exists())
+}
+
+/// Returns the longest path among a resolved path and its [`ancestors`]
+/// that exists on disk, so that callers know which directories are left
+/// to create. For a relative path of which no portion exists, an empty
+/// string, referring to the current working directory, is returned.
+///
+/// This function is only available with the `std-fs` feature.
+pub fn existing_prefix(path: &str, manipulation: OsPathManipulation) -> String {
+    let path = resolve_one(path, manipulation);
+    std::iter::once(path.clone())
+        .chain(ancestors(&path, manipulation))
+        .find(|p| std::path::Path::new(p).exists())
+        .unwrap_or_default()
+}
+
+/// Resolves a path against the current working directory if it is
+/// relative, then resolves any `..` and `.` portions. Absolute paths
+/// are only resolved.
+///
+/// Fails if the current working directory cannot be determined or
+/// is not valid Unicode.
+///
+/// This function is only available with the `std-fs` feature.
+pub fn to_absolute(path: &str, manipulation: OsPathManipulation) -> std::io::Result<String> {
+    if is_absolute(path, manipulation) {
+        return Ok(resolve_one(path, manipulation));
+    }
+    let cwd = std::env::current_dir()?;
+    let cwd = cwd.to_str().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "current directory is not valid Unicode"))?;
+    Ok(resolve(cwd, path, manipulation))
+}
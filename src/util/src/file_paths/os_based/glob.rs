@@ -0,0 +1,368 @@
+use super::*;
+
+/// Compiles a glob pattern into a regular expression matching whole
+/// resolved paths. Both `/` and `\` separate portions of the pattern.
+/// Returns `None` if the pattern is invalid, such as one with the
+/// range `[z-a]`.
+fn compile_glob(pattern: &str, manipulation: OsPathManipulation) -> Option<RegExp> {
+    compile_glob_with(pattern, manipulation, false)
+}
+
+/// Compiles a glob pattern as [`compile_glob`] does, additionally
+/// matching the paths inside a matched path if `inside` is `true`.
+fn compile_glob_with(pattern: &str, manipulation: OsPathManipulation, inside: bool) -> Option<RegExp> {
+    let pattern = pattern.replace('\\', "/");
+    let pattern = if pattern.len() > 1 { pattern.trim_end_matches('/') } else { &pattern };
+    let mut r = if manipulation == OsPathManipulation::Windows { "(?i)^".to_owned() } else { "^".to_owned() };
+    let portions: Vec<&str> = pattern.split('/').collect();
+    for (i, portion) in portions.iter().enumerate() {
+        let last = i + 1 == portions.len();
+        if *portion == "**" {
+            r.push_str(if last { ".*" } else { "(?:[^/]*/)*" });
+            continue;
+        }
+        translate_portion(portion, &mut r);
+        if !last {
+            r.push('/');
+        }
+    }
+    r.push_str(if inside { "(?:/.*)?$" } else { "$" });
+    RegExp::new(&r).ok()
+}
+
+fn translate_portion(portion: &str, r: &mut String) {
+    let chars: Vec<char> = portion.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                while chars.get(i + 1) == Some(&'*') {
+                    i += 1;
+                }
+                r.push_str("[^/]*");
+            },
+            '?' => r.push_str("[^/]"),
+            '[' if class_end(&chars, i).is_some() => {
+                let end = class_end(&chars, i).unwrap();
+                let mut j = i + 1;
+                r.push('[');
+                let negated = chars[j] == '!' || chars[j] == '^';
+                if negated {
+                    r.push('^');
+                    j += 1;
+                }
+                let start = j;
+                while j < end {
+                    if chars[j] == '-' && j != start && j + 1 != end {
+                        r.push('-');
+                    } else {
+                        r.push_str(&reg_exp::escape(&chars[j].to_string()));
+                    }
+                    j += 1;
+                }
+                // a leading `^` negates a whole intersection, so a negated
+                // set excludes `/` directly instead
+                r.push_str(if negated { "/]" } else { "&&[^/]]" });
+                i = end;
+            },
+            ch => r.push_str(&reg_exp::escape(&ch.to_string())),
+        }
+        i += 1;
+    }
+}
+
+/// Finds the `]` closing the set that starts at `chars[start]`. A `]`
+/// right after the `[` or a negation is part of the set.
+fn class_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut j = start + 1;
+    if matches!(chars.get(j), Some('!' | '^')) {
+        j += 1;
+    }
+    (j + 1..chars.len()).find(|k| chars[*k] == ']')
+}
+
+/// Indicates whether a path matches a glob pattern. The path is
+/// resolved as [`resolve_one`] does and the pattern must match it
+/// entirely.
+///
+/// Glob syntax:
+///
+/// - `*` matches any sequence of characters within a portion.
+/// - `?` matches a single character within a portion.
+/// - `[abc]`, `[a-z]` and `[!a-z]` match a character of a set.
+/// - A `**` portion matches any number of portions, including none,
+///   so `**/*.rs` matches `a.rs` and `src/a.rs`, and `target/**`
+///   matches every path inside `target`.
+///
+/// Both `/` and `\` are path separators in patterns. For the `Windows`
+/// manipulation, matching is case-insensitive. An invalid pattern, such
+/// as one with the range `[z-a]`, matches no path.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert!(os_based::matches_glob("src/a/b.rs", "src/**/*.rs", OsPathManipulation::Common));
+/// assert!(!os_based::matches_glob("src/a/b.rs", "src/*.rs", OsPathManipulation::Common));
+/// ```
+pub fn matches_glob(path: &str, pattern: &str, manipulation: OsPathManipulation) -> bool {
+    compile_glob(pattern, manipulation).is_some_and(|reg_exp| reg_exp.is_match(&resolve_one(path, manipulation).replace('\\', "/")))
+}
+
+/// Indicates whether a path matches any of several glob patterns,
+/// as [`matches_glob`] does. Each pattern is compiled at most once,
+/// and patterns after the first match are not compiled. For matching
+/// many paths against the same patterns, use [`GlobPatterns`].
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert!(os_based::matches_any_glob("target/debug/app", &["**/*.rs", "target/**"], OsPathManipulation::Common));
+/// ```
+pub fn matches_any_glob(path: &str, patterns: &[&str], manipulation: OsPathManipulation) -> bool {
+    first_matching_glob(path, patterns, manipulation).is_some()
+}
+
+/// Finds the first of several glob patterns that a path matches,
+/// as [`matches_glob`] does. Each pattern is compiled at most once,
+/// and patterns after the first match are not compiled. For matching
+/// many paths against the same patterns, use [`GlobPatterns`].
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let patterns = ["**/*.rs", "target/**"];
+/// assert_eq!(Some("target/**"), os_based::first_matching_glob("target/debug/app", &patterns, OsPathManipulation::Common));
+/// ```
+pub fn first_matching_glob<'a>(path: &str, patterns: &'a [&'a str], manipulation: OsPathManipulation) -> Option<&'a str> {
+    let path = resolve_one(path, manipulation).replace('\\', "/");
+    patterns.iter().copied().find(|pattern| compile_glob(pattern, manipulation).is_some_and(|reg_exp| reg_exp.is_match(&path)))
+}
+
+/// The `GlobPatterns` structure holds several glob patterns, each
+/// compiled once, for matching many paths against them as
+/// [`first_matching_glob`] does. An invalid pattern matches no path.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{GlobPatterns, OsPathManipulation};
+/// let patterns = GlobPatterns::new(&["**/*.rs", "target/**"], OsPathManipulation::Common);
+/// assert_eq!(Some("target/**"), patterns.first_match("target/debug/app"));
+/// assert!(!patterns.matches_any("README.md"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlobPatterns {
+    patterns: Vec<(String, Option<RegExp>)>,
+    manipulation: OsPathManipulation,
+}
+
+impl GlobPatterns {
+    /// Constructs a `GlobPatterns`, compiling each pattern once.
+    pub fn new(patterns: &[&str], manipulation: OsPathManipulation) -> Self {
+        let patterns = patterns.iter().map(|pattern| ((*pattern).to_owned(), compile_glob(pattern, manipulation))).collect();
+        Self { patterns, manipulation }
+    }
+
+    /// Indicates whether a path matches any of the patterns.
+    pub fn matches_any(&self, path: &str) -> bool {
+        self.first_match(path).is_some()
+    }
+
+    /// Finds the first of the patterns that a path matches.
+    pub fn first_match(&self, path: &str) -> Option<&str> {
+        let path = resolve_one(path, self.manipulation).replace('\\', "/");
+        self.patterns.iter()
+            .find(|(_, reg_exp)| reg_exp.as_ref().is_some_and(|reg_exp| reg_exp.is_match(&path)))
+            .map(|(pattern, _)| pattern.as_str())
+    }
+}
+
+/// The `GlobError` structure represents an error that occurred because
+/// a [`GlobSet`] pattern is invalid.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GlobError {
+    /// The invalid pattern, as given.
+    pub pattern: String,
+}
+
+impl std::fmt::Display for GlobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid glob pattern {:?}", self.pattern)
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+/// The `GlobSet` structure evaluates an ordered list of glob patterns
+/// with gitignore semantics, such as for deciding which files a build
+/// tool skips.
+///
+/// Patterns use the syntax of [`matches_glob`], with these additions:
+///
+/// - A pattern prefixed with `!` re-includes the paths it matches.
+/// - A pattern without a path separator, such as `*.rs`, matches the
+///   base name of a path at any depth.
+/// - A pattern matching a path also matches the paths inside it, so
+///   `target` ignores `target/debug/app`.
+///
+/// The last pattern that matches a path decides whether it is ignored.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{GlobSet, OsPathManipulation};
+/// let set = GlobSet::new(&["*.rs", "!keep.rs"], OsPathManipulation::Common).unwrap();
+/// assert!(set.is_ignored("src/main.rs"));
+/// assert!(!set.is_ignored("src/keep.rs"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlobSet {
+    rules: Vec<(bool, RegExp)>,
+    manipulation: OsPathManipulation,
+}
+
+impl GlobSet {
+    /// Constructs a `GlobSet`, compiling each pattern once. Empty
+    /// patterns and patterns starting with `#` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GlobError`] with the first invalid pattern, such as one
+    /// with the range `[z-a]`.
+    pub fn new(patterns: &[&str], manipulation: OsPathManipulation) -> Result<Self, GlobError> {
+        let rules = patterns.iter().filter(|p| !p.is_empty() && !p.starts_with('#')).map(|original| {
+            let (negated, pattern) = match original.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, *original),
+            };
+            let pattern = if pattern.trim_end_matches(['/', '\\']).contains(['/', '\\']) {
+                pattern.to_owned()
+            } else {
+                "**/".to_owned() + pattern
+            };
+            let reg_exp = compile_glob_with(&pattern, manipulation, true).ok_or_else(|| GlobError {
+                pattern: (*original).to_owned(),
+            })?;
+            Ok((negated, reg_exp))
+        }).collect::<Result<_, _>>()?;
+        Ok(Self { rules, manipulation })
+    }
+
+    /// Indicates whether a path is ignored, that is, whether the last
+    /// pattern that matches it is not negated. A path that matches no
+    /// pattern is not ignored.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        let path = resolve_one(path, self.manipulation).replace('\\', "/");
+        self.rules.iter().rev()
+            .find(|(_, reg_exp)| reg_exp.is_match(&path))
+            .is_some_and(|(negated, _)| !negated)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn single_glob() {
+        let common = OsPathManipulation::Common;
+        assert!(matches_glob("a.rs", "*.rs", common));
+        assert!(!matches_glob("src/a.rs", "*.rs", common));
+        assert!(matches_glob("src/a.rs", "**/*.rs", common));
+        assert!(matches_glob("a.rs", "**/*.rs", common));
+        assert!(matches_glob("a/b/c/d", "a/**/d", common));
+        assert!(matches_glob("a/d", "a/**/d", common));
+        assert!(matches_glob("./x/../a/b.txt", r"a\?.txt", common));
+        assert!(matches_glob("a/b.txt", "a/[a-c].txt", common));
+        assert!(!matches_glob("a/d.txt", "a/[a-c].txt", common));
+        assert!(matches_glob("a/d.txt", "a/[!a-c].txt", common));
+        assert!(!matches_glob("a/c", "a[!b]c", common));
+        assert!(!matches_glob("a/c", "a[^b]c", common));
+        assert!(matches_glob("axc", "a[!b]c", common));
+        assert!(!matches_glob("a/c", "a[/b]c", common));
+        assert!(matches_glob("a+(1).txt", "a+(1).txt", common));
+        assert!(matches_glob("[x", "[x", common));
+        assert!(matches_glob("[!]", "[!]", common));
+        assert!(matches_glob("]", "[]]", common));
+        assert!(matches_glob("a-", "a[x-]", common));
+        assert!(!matches_glob("a", "[z-a]", common));
+        assert!(matches_glob("a", "[a-z]", common));
+        assert!(!matches_glob("a/b", "a?b", common));
+        assert!(!matches_glob("A.RS", "*.rs", common));
+        assert!(matches_glob("/abs/x", "/abs/*", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert!(matches_glob(r"C:\Src\A.RS", "c:/src/*.rs", windows));
+        assert!(matches_glob(r"src\lib.rs", "src/*.rs", windows));
+    }
+
+    #[test]
+    fn many_globs() {
+        let common = OsPathManipulation::Common;
+        let patterns = ["**/*.rs", "target/**"];
+        assert!(matches_any_glob("src/lib.rs", &patterns, common));
+        assert!(matches_any_glob("target/debug/app", &patterns, common));
+        assert!(!matches_any_glob("target", &patterns, common));
+        assert!(!matches_any_glob("README.md", &patterns, common));
+        assert!(!matches_any_glob("a.rs", &[], common));
+        assert_eq!(Some("*.rs"), first_matching_glob("a.rs", &["[z-a]", "*.rs"], common));
+
+        assert_eq!(Some("**/*.rs"), first_matching_glob("target/x.rs", &patterns, common));
+        assert_eq!(Some("target/**"), first_matching_glob("target/x.o", &patterns, common));
+        assert_eq!(None, first_matching_glob("x.o", &patterns, common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Some("target/**"), first_matching_glob(r"Target\Debug", &patterns, windows));
+    }
+
+    #[test]
+    fn glob_patterns() {
+        let common = OsPathManipulation::Common;
+        let patterns = GlobPatterns::new(&["[z-a]", "**/*.rs", "target/**"], common);
+        assert_eq!(Some("**/*.rs"), patterns.first_match("target/x.rs"));
+        assert_eq!(Some("target/**"), patterns.first_match("./target/x.o"));
+        assert_eq!(None, patterns.first_match("x.o"));
+        assert!(patterns.matches_any("src/lib.rs"));
+        assert!(!patterns.matches_any("target"));
+        assert!(!GlobPatterns::new(&[], common).matches_any("a.rs"));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Some("TARGET/**"), GlobPatterns::new(&["TARGET/**"], windows).first_match(r"target\debug"));
+    }
+
+    #[test]
+    fn glob_sets() {
+        let common = OsPathManipulation::Common;
+        let set = GlobSet::new(&["*.rs", "!keep.rs"], common).unwrap();
+        assert!(set.is_ignored("main.rs"));
+        assert!(set.is_ignored("src/a/lib.rs"));
+        assert!(!GlobSet::new(&["a[!b]c"], common).unwrap().is_ignored("a/c"));
+        assert!(GlobSet::new(&["a[!b]c"], common).unwrap().is_ignored("x/axc"));
+        assert!(!set.is_ignored("keep.rs"));
+        assert!(!set.is_ignored("src/keep.rs"));
+        assert!(!set.is_ignored("README.md"));
+
+        let set = GlobSet::new(&["# build output", "target/", "", "!target/keep/**", "target/keep/*.o"], common).unwrap();
+        assert!(set.is_ignored("target"));
+        assert!(set.is_ignored("target/debug/app"));
+        assert!(!set.is_ignored("target/keep/notes.txt"));
+        assert!(set.is_ignored("target/keep/x.o"));
+        assert!(!set.is_ignored("src/target.rs"));
+        assert!(!set.is_ignored("# build output"));
+
+        let set = GlobSet::new(&["!*.rs", "*.rs"], common).unwrap();
+        assert!(set.is_ignored("a.rs"));
+        assert!(!GlobSet::new(&[], common).unwrap().is_ignored("a.rs"));
+
+        let windows = OsPathManipulation::Windows;
+        let set = GlobSet::new(&[r"Target\**", "*.RS", "!KEEP.rs"], windows).unwrap();
+        assert!(set.is_ignored(r"src\main.rs"));
+        assert!(!set.is_ignored(r"src\keep.RS"));
+        assert!(set.is_ignored(r"target\debug"));
+
+        assert_eq!(Err(GlobError { pattern: "![z-a]".to_owned() }), GlobSet::new(&["*.rs", "![z-a]"], common).map(|_| ()));
+    }
+}
@@ -0,0 +1,292 @@
+use super::*;
+
+/// Returns the parent directory of a resolved path, or `None` if the
+/// path is a root, such as `/`, `C:/` or `\\server\share`, or if it is
+/// a relative path with a single portion.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(Some("C:/a".to_owned()), os_based::parent(r"C:\a\b", OsPathManipulation::Windows));
+/// assert_eq!(None, os_based::parent("/", OsPathManipulation::Common));
+/// ```
+pub fn parent(path: &str, manipulation: OsPathManipulation) -> Option<String> {
+    let (prefix, mut segments) = split_prefix(path, manipulation);
+    segments.pop()?;
+    if prefix.is_empty() && segments.is_empty() {
+        return None;
+    }
+    Some(resolve_one(&(prefix + &segments.join("/")), manipulation))
+}
+
+/// Indicates whether a path is a root: `/` or, for the `Windows`
+/// manipulation, also a drive root such as `C:/` or an UNC root such
+/// as `\\server\share`. Trailing path separators are ignored.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert!(os_based::is_root(r"C:\", OsPathManipulation::Windows));
+/// assert!(!os_based::is_root("C:/", OsPathManipulation::Common));
+/// ```
+pub fn is_root(path: &str, manipulation: OsPathManipulation) -> bool {
+    let (prefix, segments) = split_prefix(path, manipulation);
+    if !segments.is_empty() || prefix.is_empty() {
+        return false;
+    }
+    if manipulation == OsPathManipulation::Windows && prefix.starts_with(r"\\") {
+        return prefix.matches('/').count() == 2;
+    }
+    prefix.ends_with('/')
+}
+
+/// Returns the ancestors of a resolved path, from its parent
+/// up to the topmost directory. The path itself is not included.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(vec!["/a/b", "/a", "/"], os_based::ancestors("/a/b/c", OsPathManipulation::Common));
+/// ```
+pub fn ancestors(path: &str, manipulation: OsPathManipulation) -> Vec<String> {
+    let mut r = Vec::<String>::new();
+    let mut path = resolve_one(path, manipulation);
+    while let Some(p) = parent(&path, manipulation) {
+        r.push(p.clone());
+        path = p;
+    }
+    r
+}
+
+/// Returns each prefix of a resolved path, from the topmost directory
+/// down to the path itself, such as for inserting the path into a trie.
+/// This is the reverse order of [`ancestors`], with the path included.
+/// An empty path has no prefixes.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(vec!["C:/", "C:/a", "C:/a/b"], os_based::prefixes(r"C:\a\b", OsPathManipulation::Windows));
+/// ```
+pub fn prefixes(path: &str, manipulation: OsPathManipulation) -> Vec<String> {
+    let path = resolve_one(path, manipulation);
+    if path.is_empty() {
+        return vec![];
+    }
+    let mut r = ancestors(&path, manipulation);
+    r.reverse();
+    r.push(path);
+    r
+}
+
+/// Returns how many levels `b` is below `a`: a positive number if `b` is
+/// inside `a`, a negative number if `a` is inside `b`, and `0` if both
+/// paths are equal, such as for indenting entries in a tree view. Returns
+/// `None` if neither path is inside the other.
+///
+/// Both paths are resolved and compared by portions, case-insensitively
+/// for the `Windows` manipulation.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(Some(2), os_based::depth_delta("/a", "/a/b/c", OsPathManipulation::Common));
+/// assert_eq!(Some(-1), os_based::depth_delta("/a/b", "/a", OsPathManipulation::Common));
+/// assert_eq!(None, os_based::depth_delta("/a/b", "/a/c", OsPathManipulation::Common));
+/// ```
+pub fn depth_delta(a: &str, b: &str, manipulation: OsPathManipulation) -> Option<i64> {
+    let (a_prefix, a_segments) = split_prefix(a, manipulation);
+    let (b_prefix, b_segments) = split_prefix(b, manipulation);
+    if !portion_eq(&a_prefix, &b_prefix, manipulation) {
+        return None;
+    }
+    if !a_segments.iter().zip(b_segments.iter()).all(|(x, y)| portion_eq(x, y, manipulation)) {
+        return None;
+    }
+    Some(b_segments.len() as i64 - a_segments.len() as i64)
+}
+
+/// Returns the mount point of a path among the given mounts: the mount
+/// that is the path or the deepest ancestor of it, such as for labeling
+/// a path by its volume. Returns `None` if no mount contains the path.
+///
+/// Paths are compared as [`depth_delta`] does.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let mounts = ["/", "/home", "/mnt/usb"];
+/// assert_eq!(Some("/home"), os_based::mount_of("/home/u/a.txt", &mounts, OsPathManipulation::Common));
+/// assert_eq!(Some("/"), os_based::mount_of("/mnt/usb2", &mounts, OsPathManipulation::Common));
+/// ```
+pub fn mount_of<'a>(path: &str, mounts: &'a [&'a str], manipulation: OsPathManipulation) -> Option<&'a str> {
+    mounts.iter().copied()
+        .filter(|mount| depth_delta(mount, path, manipulation).is_some_and(|delta| delta >= 0))
+        .max_by_key(|mount| split_prefix(mount, manipulation).1.len())
+}
+
+/// Splits a resolved path into its root or prefix, such as `/`, `C:/`
+/// or `\\server/share/`, and its portions, excluding path separators.
+/// The prefix is `None` for a relative path. Trailing path separators
+/// produce no empty final portion.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!((Some("C:/".to_owned()), vec!["a".to_owned(), "b".to_owned()]), os_based::explode(r"C:\a\b\", OsPathManipulation::Windows));
+/// ```
+pub fn explode(path: &str, manipulation: OsPathManipulation) -> (Option<String>, Vec<String>) {
+    let (prefix, segments) = split_prefix(path, manipulation);
+    (Some(prefix).filter(|p| !p.is_empty()), segments)
+}
+
+/// Resolves `rel` relative to the directory containing `file_path`,
+/// as relative links in a document are resolved. If `file_path` is a
+/// relative path with a single portion, `rel` is resolved relative to
+/// the empty path, and if it is a root, relative to the root itself.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("/a/b/sibling.txt", os_based::resolve_from_file("/a/b/file.txt", "sibling.txt", OsPathManipulation::Common));
+/// ```
+pub fn resolve_from_file(file_path: &str, rel: &str, manipulation: OsPathManipulation) -> String {
+    let directory = parent(file_path, manipulation).unwrap_or_else(|| {
+        if is_root(file_path, manipulation) { resolve_one(file_path, manipulation) } else { "".to_owned() }
+    });
+    resolve(&directory, rel, manipulation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mounts() {
+        let common = OsPathManipulation::Common;
+        let mounts = ["/home", "/", "/home/u/data", "/mnt/usb"];
+        assert_eq!(Some("/home"), mount_of("/home/u/a.txt", &mounts, common));
+        assert_eq!(Some("/home/u/data"), mount_of("/home/u/data/x/y", &mounts, common));
+        assert_eq!(Some("/home/u/data"), mount_of("/home/u/data", &mounts, common));
+        assert_eq!(Some("/"), mount_of("/etc/hosts", &mounts, common));
+        assert_eq!(Some("/"), mount_of("/mnt/usb2", &mounts, common));
+        assert_eq!(Some("/mnt/usb"), mount_of("/mnt/./usb/../usb/f", &mounts, common));
+        assert_eq!(None, mount_of("relative/x", &mounts, common));
+        assert_eq!(None, mount_of("/home/x", &[], common));
+
+        let windows = OsPathManipulation::Windows;
+        let mounts = ["C:/", r"C:\Mount\Disk", "D:/"];
+        assert_eq!(Some(r"C:\Mount\Disk"), mount_of("c:/mount/disk/file", &mounts, windows));
+        assert_eq!(Some("C:/"), mount_of(r"C:\Mount", &mounts, windows));
+        assert_eq!(None, mount_of("E:/x", &mounts, windows));
+    }
+
+    #[test]
+    fn depth_deltas() {
+        let common = OsPathManipulation::Common;
+        assert_eq!(Some(2), depth_delta("/a", "/a/b/c", common));
+        assert_eq!(Some(-2), depth_delta("/a/b/c", "/a", common));
+        assert_eq!(Some(0), depth_delta("/a/b", "/a/./b/", common));
+        assert_eq!(Some(3), depth_delta("/", "/x/y/z", common));
+        assert_eq!(None, depth_delta("/a/b", "/a/c/d", common));
+        assert_eq!(None, depth_delta("/ab", "/a/b", common));
+        assert_eq!(None, depth_delta("/a", "a", common));
+        assert_eq!(Some(1), depth_delta("a", "a/b", common));
+        assert_eq!(Some(1), depth_delta("", "a", common));
+        assert_eq!(None, depth_delta("/a", "/A/b", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Some(1), depth_delta(r"C:\Users", "c:/users/x", windows));
+        assert_eq!(Some(-1), depth_delta(r"\\srv\share\a", r"\\srv\share", windows));
+        assert_eq!(None, depth_delta("C:/a", "D:/a/b", windows));
+    }
+
+    #[test]
+    fn path_prefixes() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(vec!["C:/", "C:/a", "C:/a/b"], prefixes("C:/a/b", windows));
+        assert_eq!(vec!["C:/"], prefixes(r"C:\", windows));
+        let common = OsPathManipulation::Common;
+        assert_eq!(vec!["/", "/a", "/a/b"], prefixes("/a/./b/", common));
+        assert_eq!(vec!["/"], prefixes("/", common));
+        assert_eq!(vec!["a", "a/b", "a/b/c"], prefixes("a/b/x/../c", common));
+        assert_eq!(vec!["a"], prefixes("a", common));
+        assert!(prefixes("", common).is_empty());
+        assert!(prefixes("a/..", common).is_empty());
+    }
+
+    #[test]
+    fn hierarchy() {
+        let common = OsPathManipulation::Common;
+        assert_eq!(Some("/a".to_owned()), parent("/a/b/", common));
+        assert_eq!(Some("/".to_owned()), parent("/a", common));
+        assert_eq!(Some("a".to_owned()), parent("a/b", common));
+        assert_eq!(None, parent("a", common));
+        assert_eq!(None, parent("", common));
+        assert_eq!(vec!["a/b", "a"], ancestors("a/b/c", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Some("C:/".to_owned()), parent("C:/a", windows));
+        assert_eq!(None, parent("C:/", windows));
+        assert_eq!(Some(r"\\server/share".to_owned()), parent(r"\\server\share\a", windows));
+        assert_eq!(None, parent(r"\\server\share", windows));
+        assert_eq!(vec!["C:/a/b", "C:/a", "C:/"], ancestors(r"C:\a\b\c", windows));
+    }
+
+    #[test]
+    fn exploding() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!((Some("C:/".to_owned()), vec!["Users".to_owned(), "a".to_owned()]), explode(r"C:\Users\a\", windows));
+        assert_eq!((Some(r"\\srv/share/".to_owned()), vec!["x".to_owned()]), explode(r"\\srv\share\x", windows));
+        assert_eq!((Some("C:/".to_owned()), vec![]), explode("C:/", windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!((None, vec!["a".to_owned(), "c".to_owned()]), explode("a/b/../c/", common));
+        assert_eq!((Some("/".to_owned()), vec!["a".to_owned()]), explode("/a", common));
+        assert_eq!((None, vec![]), explode("", common));
+    }
+
+    #[test]
+    fn from_file() {
+        let common = OsPathManipulation::Common;
+        assert_eq!("/a/b/sibling.txt", resolve_from_file("/a/b/file.txt", "sibling.txt", common));
+        assert_eq!("/a/x", resolve_from_file("/a/b/file.txt", "../x", common));
+        assert_eq!("/etc/x", resolve_from_file("/a/b/file.txt", "/etc/x", common));
+        assert_eq!("docs/img.png", resolve_from_file("docs/readme.md", "./img.png", common));
+        assert_eq!("img.png", resolve_from_file("readme.md", "img.png", common));
+        assert_eq!("/x", resolve_from_file("/", "x", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("C:/a/x.txt", resolve_from_file(r"C:\a\b\f.txt", r"..\x.txt", windows));
+    }
+
+    #[test]
+    fn roots() {
+        let common = OsPathManipulation::Common;
+        assert!(is_root("/", common));
+        assert!(is_root("//", common));
+        assert!(!is_root("/a", common));
+        assert!(!is_root("", common));
+        assert!(!is_root("C:/", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert!(is_root("/", windows));
+        assert!(is_root("C:/", windows));
+        assert!(is_root(r"c:\", windows));
+        assert!(is_root(r"\\server\share", windows));
+        assert!(is_root(r"\\server\share\", windows));
+        assert!(is_root("C:", windows), "resolved as `C:/`");
+        assert!(!is_root("C:/a", windows));
+        assert!(!is_root(r"\\server", windows));
+        assert!(!is_root(r"\\server\share\a", windows));
+        assert!(!is_root("a", windows));
+    }
+}
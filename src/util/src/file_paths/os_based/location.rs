@@ -0,0 +1,78 @@
+use super::*;
+
+/// The `Location` structure represents a location in a file, as in
+/// the `src/main.rs:10:5` form that compilers and tools output.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    /// The path, as it is given.
+    pub path: String,
+    /// The line number, if given.
+    pub line: Option<usize>,
+    /// The column number, if given. There is no column without a line.
+    pub column: Option<usize>,
+}
+
+/// Parses a `path`, `path:line` or `path:line:column` location string.
+///
+/// For the `Windows` manipulation, the colon of a drive prefix is never
+/// taken as a line separator, so that `C:\x:10` is the line 10 of `C:\x`.
+/// Returns `None` if the path is empty.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, Location, OsPathManipulation};
+/// let location = os_based::parse_location("src/main.rs:10:5", OsPathManipulation::Common).unwrap();
+/// assert_eq!(Location { path: "src/main.rs".into(), line: Some(10), column: Some(5) }, location);
+/// ```
+pub fn parse_location(s: &str, manipulation: OsPathManipulation) -> Option<Location> {
+    let mut path = s;
+    let mut numbers = Vec::<usize>::new();
+    while numbers.len() < 2 {
+        let Some((rest, number)) = path.rsplit_once(':') else {
+            break;
+        };
+        let is_drive = manipulation == OsPathManipulation::Windows && rest.len() == 1 && rest.as_bytes()[0].is_ascii_alphabetic();
+        let number = number.parse::<usize>().ok().filter(|_| number.bytes().all(|b| b.is_ascii_digit()));
+        let Some(number) = number.filter(|_| !rest.is_empty() && !is_drive) else {
+            break;
+        };
+        numbers.insert(0, number);
+        path = rest;
+    }
+    if path.is_empty() {
+        return None;
+    }
+    Some(Location { path: path.to_owned(), line: numbers.first().copied(), column: numbers.get(1).copied() })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn location(path: &str, line: Option<usize>, column: Option<usize>) -> Option<Location> {
+        Some(Location { path: path.to_owned(), line, column })
+    }
+
+    #[test]
+    fn locations() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(location(r"C:\x", Some(10), None), parse_location(r"C:\x:10", windows));
+        assert_eq!(location(r"C:\src\main.rs", Some(10), Some(5)), parse_location(r"C:\src\main.rs:10:5", windows));
+        assert_eq!(location(r"C:\x", None, None), parse_location(r"C:\x", windows));
+        assert_eq!(location("C:", None, None), parse_location("C:", windows));
+        assert_eq!(location("C:12", None, None), parse_location("C:12", windows));
+        assert_eq!(location("C:12", Some(3), None), parse_location("C:12:3", windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!(location("src/main.rs", Some(10), Some(5)), parse_location("src/main.rs:10:5", common));
+        assert_eq!(location("a:1", Some(2), Some(3)), parse_location("a:1:2:3", common));
+        assert_eq!(location("a.rs", Some(7), None), parse_location("a.rs:7", common));
+        assert_eq!(location("a.rs:x", None, None), parse_location("a.rs:x", common));
+        assert_eq!(location("a.rs:", None, None), parse_location("a.rs:", common));
+        assert_eq!(location("C", Some(12), None), parse_location("C:12", common));
+        assert_eq!(None, parse_location("", common));
+        assert_eq!(location(":10", None, None), parse_location(":10", common));
+        assert_eq!(location("a:+1", None, None), parse_location("a:+1", common));
+    }
+}
@@ -0,0 +1,212 @@
+/*!
+Work with path strings based on a manipulation.
+
+The manipulation ([`OsPathManipulation`]) indicates whether Windows absolute
+paths, which start with either a drive letter followed by a colon
+or an UNC prefix (`\\`), are considered. The `Common` manipulation
+behaves the same on every platform.
+
+# Example
+
+```
+use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+
+assert_eq!("C:/a/c", os_based::resolve("C:/a/b", "../c", OsPathManipulation::Windows));
+assert_eq!("../c", os_based::relative("/a/b", "/a/c", OsPathManipulation::Common));
+```
+*/
+
+use crate::reg_exp::*;
+use super::Path;
+
+pub use super::PlatformPathVariant as OsPathManipulation;
+
+mod relativity;
+pub use relativity::{
+    rebase,
+    relative_many,
+    relative_opts,
+    simplify_relative,
+    RelativeOptions,
+};
+
+mod classification;
+pub use classification::{ensure_absolute, windows_path_kind, WindowsPathKind};
+
+mod normalization;
+pub use normalization::{
+    dedup_paths,
+    equality_key,
+    normalize_and_sort,
+    normalize_drive_case,
+    normalize_drive_case_to,
+    normalize_reporting,
+    DriveLetterCase,
+};
+
+mod path_set;
+pub use path_set::PathSet;
+
+mod template;
+pub use template::interpolate_template;
+
+mod typed;
+pub use typed::{AbsolutePath, PathKindError, RelativePath};
+
+mod hierarchy;
+pub use hierarchy::{
+    ancestors,
+    depth_delta,
+    explode,
+    is_root,
+    mount_of,
+    parent,
+    prefixes,
+    resolve_from_file,
+};
+
+mod display;
+pub use display::{shorten_home, tail, truncate_middle};
+
+mod location;
+pub use location::{parse_location, Location};
+
+mod native;
+pub use native::{
+    dual_form,
+    relative_native,
+    resolve_preserving_style,
+    to_native,
+    to_portable,
+};
+
+mod path_list;
+pub use path_list::{join_path_list, split_path_list};
+
+mod decoding;
+pub use decoding::{resolve_decoding, DecodeError};
+
+mod sandbox;
+pub use sandbox::{resolve_within_roots, Chroot, EscapeError, SandboxError};
+
+mod glob;
+pub use glob::{first_matching_glob, matches_any_glob, matches_glob, GlobError, GlobPatterns, GlobSet};
+
+mod validation;
+pub use validation::{is_plain_file_name, validate_path_chars, PathCharError};
+
+mod prefix;
+pub use prefix::{
+    is_absolute_with_matcher,
+    prefix_matcher,
+    resolve_keep_double_slash,
+    resolve_with_matcher,
+    PrefixMatcher,
+};
+
+pub mod serde_default;
+pub mod serde_windows;
+
+#[cfg(feature = "std-fs")]
+mod fs;
+#[cfg(feature = "std-fs")]
+pub use fs::{existing_prefix, find_up, to_absolute};
+
+static STARTS_WITH_PATH_SEPARATOR: StaticRegExp = static_reg_exp!(r"^[/\\]");
+
+static STARTS_WITH_WINDOWS_PATH_PREFIX_OR_SLASH: StaticRegExp = static_reg_exp!(r#"(?x)
+    ^ (
+        (\\\\)             | # UNC prefix
+        ([A-Za-z]\:)       | # drive prefix
+        [\/\\] ([^\\] | $)   # slash
+    )
+"#);
+
+/// Resolves `path2` relative to `path1`.
+///
+/// Behavior:
+/// - Eliminates the portions `..` and `.`.
+/// - If `path2` is absolute, this function returns a resolution of solely `path2`.
+/// - All path separators that are backslashes (`\`) are replaced by forward ones (`/`).
+/// - If any path is absolute, this function returns an absolute path.
+/// - Any empty portion and trailing path separators, such as in `a/b/` and `a//b` are eliminated.
+pub fn resolve(path1: &str, path2: &str, manipulation: OsPathManipulation) -> String {
+    Path::new(path1, manipulation).resolve(path2).to_string()
+}
+
+/// Resolves multiple paths. The behavior is similiar to [`resolve`].
+/// If the given set has no items, an empty string is returned.
+pub fn resolve_n<'a, T: IntoIterator<Item = &'a str>>(paths: T, manipulation: OsPathManipulation) -> String {
+    let paths = paths.into_iter().collect::<Vec<&'a str>>();
+    if paths.is_empty() {
+        return "".to_owned();
+    }
+    Path::from_n(paths, manipulation).to_string()
+}
+
+/// Resolves a single path, eliminating the portions `..` and `.`.
+pub fn resolve_one(path: &str, manipulation: OsPathManipulation) -> String {
+    Path::new(path, manipulation).to_string()
+}
+
+/// Indicates whether a path is absolute or not.
+pub fn is_absolute(path: &str, manipulation: OsPathManipulation) -> bool {
+    match manipulation {
+        OsPathManipulation::Common => STARTS_WITH_PATH_SEPARATOR.is_match(path),
+        OsPathManipulation::Windows => STARTS_WITH_WINDOWS_PATH_PREFIX_OR_SLASH.is_match(path),
+    }
+}
+
+/// Indicates whether a path is relative or not.
+pub fn is_relative(path: &str, manipulation: OsPathManipulation) -> bool {
+    !is_absolute(path, manipulation)
+}
+
+/// Finds the relative path from `from_path` to `to_path`.
+///
+/// # Behavior:
+///
+/// - If the paths refer to the same path, this function returns
+///   an empty string.
+/// - The function ensures that both paths are absolute and resolves
+///   any `..` and `.` portions inside.
+/// - If both paths have different prefix, `to_path` is returned.
+///
+/// # Panics
+///
+/// Panics if given paths are not absolute.
+pub fn relative(from_path: &str, to_path: &str, manipulation: OsPathManipulation) -> String {
+    Path::new(from_path, manipulation).relative(to_path)
+}
+
+/// Splits a path into its resolved prefix and its segments.
+///
+/// The prefix is empty for a relative path, `/` for a path starting
+/// with a path separator, and for the `Windows` manipulation, either
+/// a drive prefix such as `C:/` or an UNC prefix including the server
+/// and share names, such as `\\server/share/`.
+pub(crate) fn split_prefix(path: &str, manipulation: OsPathManipulation) -> (String, Vec<String>) {
+    let resolved = resolve_one(path, manipulation);
+    if manipulation == OsPathManipulation::Windows && resolved.starts_with(r"\\") {
+        let mut segments: Vec<String> = resolved[2..].split('/').filter(|s| !s.is_empty()).map(|s| s.to_owned()).collect();
+        let root: Vec<String> = segments.drain(..usize::min(2, segments.len())).collect();
+        let prefix = if root.is_empty() { r"\\".to_owned() } else { r"\\".to_owned() + &root.join("/") + "/" };
+        return (prefix, segments);
+    }
+    let (prefix, rest) = if manipulation == OsPathManipulation::Windows && reg_exp_is_match!(r"^[A-Za-z]:", &resolved) {
+        resolved.split_at(if resolved[2..].starts_with('/') { 3 } else { 2 })
+    } else if resolved.starts_with('/') {
+        resolved.split_at(1)
+    } else {
+        ("", resolved.as_str())
+    };
+    (prefix.to_owned(), rest.split('/').filter(|s| !s.is_empty()).map(|s| s.to_owned()).collect())
+}
+
+/// Compares two path portions, ignoring case for the `Windows` manipulation.
+pub(crate) fn portion_eq(a: &str, b: &str, manipulation: OsPathManipulation) -> bool {
+    match manipulation {
+        OsPathManipulation::Common => a == b,
+        OsPathManipulation::Windows => a.to_lowercase() == b.to_lowercase(),
+    }
+}
@@ -0,0 +1,161 @@
+use super::*;
+use crate::file_paths::{separator_style, SeparatorStyle};
+
+/// Converts the path separators of a path to the native separator
+/// of the manipulation: backslashes (`\`) for the `Windows`
+/// manipulation and forward slashes (`/`) for the `Common` manipulation.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(r"C:\a\b", os_based::to_native("C:/a/b", OsPathManipulation::Windows));
+/// ```
+pub fn to_native(path: &str, manipulation: OsPathManipulation) -> String {
+    match manipulation {
+        OsPathManipulation::Common => path.replace('\\', "/"),
+        OsPathManipulation::Windows => path.replace('/', "\\"),
+    }
+}
+
+/// Returns the portable form of a path, for storing it in a
+/// cross-platform way regardless of the manipulation.
+///
+/// Backslashes are replaced by forward slashes, consecutive separators
+/// are collapsed into one and trailing separators are removed. For the
+/// `Windows` manipulation, the drive letter is uppercased and a leading
+/// UNC prefix is kept as `//`. Unlike [`resolve_one`], the `.` and `..`
+/// portions are left as they are.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("C:/a/../b", os_based::to_portable(r"c:\a\\..\b\", OsPathManipulation::Windows));
+/// ```
+pub fn to_portable(path: &str, manipulation: OsPathManipulation) -> String {
+    let mut path = path.replace('\\', "/");
+    let unc = manipulation == OsPathManipulation::Windows && path.starts_with("//");
+    if manipulation == OsPathManipulation::Windows {
+        path = normalize_drive_case(&path);
+    }
+    let mut r = reg_exp_replace_all!(r"/{2,}", &path, "/").into_owned();
+    let is_root = |r: &str| r == "/" || (manipulation == OsPathManipulation::Windows && reg_exp_is_match!(r"^[A-Za-z]:/$", r));
+    while r.ends_with('/') && !is_root(&r) {
+        r.pop();
+    }
+    if unc { "/".to_owned() + &r } else { r }
+}
+
+/// Returns both the forward-slash and the Windows form of a path,
+/// such as for cross-platform manifests that store both.
+///
+/// The forward-slash form is the portable form given by [`to_portable`]
+/// for the `Windows` manipulation, with the drive letter uppercased and
+/// separators collapsed, and the Windows form is the same path with
+/// backslashes. `.` and `..` portions are left as they are.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based;
+/// assert_eq!(("C:/a/b".to_owned(), r"C:\a\b".to_owned()), os_based::dual_form(r"c:\a/b\"));
+/// ```
+pub fn dual_form(path: &str) -> (String, String) {
+    let posix = to_portable(path, OsPathManipulation::Windows);
+    let windows = to_native(&posix, OsPathManipulation::Windows);
+    (posix, windows)
+}
+
+/// Finds the relative path from `from_path` to `to_path` as [`relative`]
+/// does, then converts it to native separators using [`to_native`].
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(r"..\..\foo", os_based::relative_native(r"C:\a\b", r"C:\foo", OsPathManipulation::Windows));
+/// ```
+///
+/// # Panics
+///
+/// Panics if given paths are not absolute.
+pub fn relative_native(from_path: &str, to_path: &str, manipulation: OsPathManipulation) -> String {
+    to_native(&relative(from_path, to_path, manipulation), manipulation)
+}
+
+/// Resolves `path2` relative to `path1` as [`resolve`] does, keeping
+/// the separator style of the inputs: if every path separator in the
+/// inputs is a backslash (`\`), the result uses backslashes. Otherwise,
+/// such as when the inputs mix both separators, the result uses
+/// forward slashes (`/`).
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(r"C:\a\c", os_based::resolve_preserving_style(r"C:\a\b", r"..\c", OsPathManipulation::Windows));
+/// assert_eq!("C:/a/c", os_based::resolve_preserving_style(r"C:\a\b", "../c", OsPathManipulation::Windows));
+/// ```
+pub fn resolve_preserving_style(path1: &str, path2: &str, manipulation: OsPathManipulation) -> String {
+    let r = resolve(path1, path2, manipulation);
+    let styles = [separator_style(path1), separator_style(path2)];
+    let backslash = styles.contains(&SeparatorStyle::BackslashOnly)
+        && styles.iter().all(|s| matches!(s, SeparatorStyle::BackslashOnly | SeparatorStyle::None));
+    if backslash { r.replace('/', "\\") } else { r }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn native() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(r"..\c\d", relative_native("C:/a/b", "C:/a/c/d", windows));
+        assert_eq!(r"D:\x", relative_native("C:/a", "D:/x", windows));
+        assert_eq!("", relative_native("C:/a", "C:/a", windows));
+        let common = OsPathManipulation::Common;
+        assert_eq!("../c/d", relative_native("/a/b", "/a/c/d", common));
+        assert_eq!("a/b", to_native(r"a\b", common));
+    }
+
+    #[test]
+    fn preserving_style() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(r"C:\a\c", resolve_preserving_style(r"C:\a\b", r"..\c", windows));
+        assert_eq!(r"C:\a\b\c", resolve_preserving_style(r"C:\a\b\", "c", windows));
+        assert_eq!(r"\\srv\share\x", resolve_preserving_style(r"\\srv\share", "x", windows));
+        assert_eq!("C:/a/c", resolve_preserving_style(r"C:\a\b", "../c", windows));
+        assert_eq!("C:/a/b/c", resolve_preserving_style(r"C:\a/b", "c", windows));
+        assert_eq!("C:/x", resolve_preserving_style("C:/a", r"C:\x", windows));
+        assert_eq!("a/b", resolve_preserving_style("a", "b", windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!(r"\a\c", resolve_preserving_style(r"\a\b", r"..\c", common));
+        assert_eq!("/a/b/c", resolve_preserving_style("/a/b", "c", common));
+    }
+
+    #[test]
+    fn dual_forms() {
+        assert_eq!(("C:/Users/a".to_owned(), r"C:\Users\a".to_owned()), dual_form(r"c:\Users//a"));
+        assert_eq!(("C:/".to_owned(), r"C:\".to_owned()), dual_form("C:/"));
+        assert_eq!(("src/lib.rs".to_owned(), r"src\lib.rs".to_owned()), dual_form(r"src\lib.rs"));
+        assert_eq!(("../x".to_owned(), r"..\x".to_owned()), dual_form("../x/"));
+        assert_eq!(("//srv/share".to_owned(), r"\\srv\share".to_owned()), dual_form(r"\\srv\share"));
+    }
+
+    #[test]
+    fn portable() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("C:/Users/x/../y", to_portable(r"c:\Users\\x\..\y\\", windows));
+        assert_eq!("C:/", to_portable(r"c:\", windows));
+        assert_eq!("//server/share/a", to_portable(r"\\server\share\\a", windows));
+        assert_eq!("./a", to_portable(r".\a", windows));
+        let common = OsPathManipulation::Common;
+        assert_eq!("/home/u/./docs", to_portable("//home///u/./docs/", common));
+        assert_eq!("/", to_portable("///", common));
+        assert_eq!("c:/a", to_portable("c:/a", common));
+        assert_eq!("", to_portable("", common));
+    }
+}
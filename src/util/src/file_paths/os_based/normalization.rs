@@ -0,0 +1,221 @@
+use super::*;
+
+/// The letter case of a Windows drive letter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DriveLetterCase {
+    /// An uppercase drive letter, such as in `C:/`.
+    #[default]
+    Upper,
+    /// A lowercase drive letter, such as in `c:/`.
+    Lower,
+}
+
+/// Uppercases the drive letter of a Windows path, leaving
+/// everything else untouched.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based;
+/// assert_eq!("C:/X", os_based::normalize_drive_case("c:/X"));
+/// ```
+pub fn normalize_drive_case(path: &str) -> String {
+    normalize_drive_case_to(path, DriveLetterCase::Upper)
+}
+
+/// Changes the drive letter of a Windows path to the given case,
+/// leaving everything else untouched. The drive letter of an
+/// extended-length path, such as `\\?\c:\x`, is also changed.
+pub fn normalize_drive_case_to(path: &str, case: DriveLetterCase) -> String {
+    reg_exp_replace!(r"^([/\\]{2}\?[/\\])?([A-Za-z]):", path, |_, prefix: &str, letter: &str| {
+        let letter = match case {
+            DriveLetterCase::Upper => letter.to_ascii_uppercase(),
+            DriveLetterCase::Lower => letter.to_ascii_lowercase(),
+        };
+        format!("{}{}:", prefix, letter)
+    }).into_owned()
+}
+
+/// Resolves a single path as [`resolve_one`] does, additionally
+/// indicating whether the result differs from the given path.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(("a/c".to_owned(), true), os_based::normalize_reporting("a/b/../c/", OsPathManipulation::Common));
+/// assert_eq!(("a/c".to_owned(), false), os_based::normalize_reporting("a/c", OsPathManipulation::Common));
+/// ```
+pub fn normalize_reporting(path: &str, manipulation: OsPathManipulation) -> (String, bool) {
+    let r = resolve_one(path, manipulation);
+    let changed = r != path;
+    (r, changed)
+}
+
+/// Computes a key identifying the path's equality class, suitable
+/// for `HashMap` and `HashSet` keys: the path is resolved as [`resolve_one`]
+/// does and, for the `Windows` manipulation, lowercased. The key is
+/// meant for keying only, not for display.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let windows = OsPathManipulation::Windows;
+/// assert_eq!(os_based::equality_key(r"C:\Users\A", windows), os_based::equality_key("c:/users/x/../a/", windows));
+/// ```
+pub fn equality_key(path: &str, manipulation: OsPathManipulation) -> String {
+    let path = resolve_one(path, manipulation);
+    match manipulation {
+        OsPathManipulation::Common => path,
+        OsPathManipulation::Windows => path.to_lowercase(),
+    }
+}
+
+/// Resolves each path and removes semantic duplicates, preserving
+/// the order in which paths are first seen. For the `Windows`
+/// manipulation, paths that differ only in case are duplicates.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let paths = os_based::dedup_paths([r"C:\a\b", "C:/a/b", "c:/A/./b"], OsPathManipulation::Windows);
+/// assert_eq!(vec!["C:/a/b"], paths);
+/// ```
+pub fn dedup_paths<'a, I: IntoIterator<Item = &'a str>>(paths: I, manipulation: OsPathManipulation) -> Vec<String> {
+    let mut seen = crate::collections::Set::<String>::new();
+    let mut r = Vec::<String>::new();
+    for path in paths {
+        if seen.insert(equality_key(path, manipulation)) {
+            r.push(resolve_one(path, manipulation));
+        }
+    }
+    r
+}
+
+/// A sort key for [`normalize_and_sort`], keying each portion by whether
+/// it is the last one, its folded name and its exact name.
+type SortKey = Vec<(bool, String, String)>;
+
+/// Resolves each path and sorts the paths portion by portion, for a
+/// reproducible ordering such as in manifests.
+///
+/// Paths are compared portion by portion: a portion that is a directory,
+/// because more portions follow it, comes before one that is the last
+/// portion, and otherwise portions are ordered by name. Therefore the
+/// paths inside a directory come before the directory itself and the
+/// files next to it. Relative paths come before
+/// absolute paths, which are grouped by prefix. For the `Windows`
+/// manipulation, names are compared case-insensitively, and only by
+/// case as a tiebreaker.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let paths = os_based::normalize_and_sort(["b.txt", r"a\z.txt", "a/b/c.txt", "a"], OsPathManipulation::Common);
+/// assert_eq!(vec!["a/b/c.txt", "a/z.txt", "a", "b.txt"], paths);
+/// ```
+pub fn normalize_and_sort<'a, I: IntoIterator<Item = &'a str>>(paths: I, manipulation: OsPathManipulation) -> Vec<String> {
+    let fold = |s: &str| match manipulation {
+        OsPathManipulation::Common => s.to_owned(),
+        OsPathManipulation::Windows => s.to_lowercase(),
+    };
+    let mut entries: Vec<(String, (String, String), SortKey)> = paths.into_iter().map(|path| {
+        let (prefix, segments) = split_prefix(path, manipulation);
+        let last = segments.len().saturating_sub(1);
+        let key = segments.into_iter().enumerate().map(|(i, s)| (i == last, fold(&s), s)).collect();
+        (resolve_one(path, manipulation), (fold(&prefix), prefix), key)
+    }).collect();
+    entries.sort_by(|(_, a_prefix, a), (_, b_prefix, b)| a_prefix.cmp(b_prefix).then_with(|| a.cmp(b)));
+    entries.into_iter().map(|(path, _, _)| path).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dedup() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(vec!["C:/a/b", "D:/x"], dedup_paths([r"C:\a\b", "D:/x", "C:/a/b", "C:/a/./b", "c:/A/B/", "d:/x/y/.."], windows));
+        let common = OsPathManipulation::Common;
+        assert_eq!(vec!["/a/b", "/A/b", "c"], dedup_paths(["/a/b", "/A/b", r"\a\b", "c", "./c"], common));
+    }
+
+    #[test]
+    fn equality_keys() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(equality_key(r"C:\a\B", windows), equality_key("c:/a/./x/../b/", windows));
+        assert_ne!(equality_key("C:/a", windows), equality_key("D:/a", windows));
+        let common = OsPathManipulation::Common;
+        assert_eq!(equality_key("/a/b", common), equality_key(r"\a//b/./", common));
+        assert_ne!(equality_key("/a/b", common), equality_key("/A/b", common));
+
+        let mut map = std::collections::HashMap::<String, u32>::new();
+        map.insert(equality_key("C:/Data", windows), 1);
+        assert_eq!(Some(&1), map.get(&equality_key(r"c:\data\", windows)));
+    }
+
+    #[test]
+    fn sorting() {
+        let common = OsPathManipulation::Common;
+        let expected = vec!["a/b/c.txt", "a/b/d", "a/z.txt", "a", "a.txt", "b", "/x"];
+        assert_eq!(expected, normalize_and_sort(["b", "a.txt", "a/z.txt", "a/b/d", "a/b/c.txt", "a", "/x/"], common));
+        assert_eq!(expected, normalize_and_sort([r"a\b\d", "a/./z.txt", "/x", "b/", r"a\b\c.txt", "a.txt", "a//"], common));
+        assert_eq!(vec!["B", "a"], normalize_and_sort(["a", "B"], common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(vec!["C:/a/x", "C:/B", "c:/b", "D:/a"], normalize_and_sort(["D:/a", "c:/b", r"C:\B", r"C:\a\x"], windows));
+        assert_eq!(vec!["a", "B"], normalize_and_sort(["B", "a"], windows));
+    }
+
+    #[test]
+    fn sorting_is_order_independent() {
+        fn permutations(items: &[&'static str]) -> Vec<Vec<&'static str>> {
+            if items.is_empty() {
+                return vec![vec![]];
+            }
+            (0..items.len()).flat_map(|i| {
+                let mut rest = items.to_vec();
+                let first = rest.remove(i);
+                permutations(&rest).into_iter().map(move |mut p| {
+                    p.insert(0, first);
+                    p
+                })
+            }).collect()
+        }
+        let common = OsPathManipulation::Common;
+        let items = ["a", "a/b/c.txt", "b/x", "a.txt", "/z", "b"];
+        let expected = normalize_and_sort(items, common);
+        for p in permutations(&items) {
+            assert_eq!(expected, normalize_and_sort(p, common));
+        }
+        let windows = OsPathManipulation::Windows;
+        let items = ["C:/a", "c:/A/b", "C:/B/x", "a", "D:/"];
+        let expected = normalize_and_sort(items, windows);
+        for p in permutations(&items) {
+            assert_eq!(expected, normalize_and_sort(p, windows));
+        }
+    }
+
+    #[test]
+    fn reporting() {
+        let common = OsPathManipulation::Common;
+        assert_eq!(("/a/b".to_owned(), false), normalize_reporting("/a/b", common));
+        assert_eq!(("/a/b".to_owned(), true), normalize_reporting("/a//./b/", common));
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(("C:/a".to_owned(), false), normalize_reporting("C:/a", windows));
+        assert_eq!(("C:/a".to_owned(), true), normalize_reporting(r"C:\a", windows));
+    }
+
+    #[test]
+    fn drive_case() {
+        assert_eq!("C:/X", normalize_drive_case("c:/X"));
+        assert_eq!(r"C:\a\B", normalize_drive_case(r"C:\a\B"));
+        assert_eq!(r"\\?\D:\x", normalize_drive_case(r"\\?\d:\x"));
+        assert_eq!("a/c:/x", normalize_drive_case("a/c:/x"));
+        assert_eq!("c:/X", normalize_drive_case_to("C:/X", DriveLetterCase::Lower));
+    }
+}
@@ -0,0 +1,88 @@
+use super::*;
+
+fn list_separator(manipulation: OsPathManipulation) -> char {
+    match manipulation {
+        OsPathManipulation::Common => ':',
+        OsPathManipulation::Windows => ';',
+    }
+}
+
+/// Splits a path list, such as the value of the `PATH` environment
+/// variable, into its entries. Entries are separated by `;` for the
+/// `Windows` manipulation and by `:` otherwise. Empty entries are
+/// skipped and entries are not resolved.
+///
+/// For the `Windows` manipulation, double quotes may enclose parts of
+/// an entry that contain `;`, and are removed.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(vec![r"C:\a", r"C:\b"], os_based::split_path_list(r"C:\a;C:\b", OsPathManipulation::Windows));
+/// ```
+pub fn split_path_list(list: &str, manipulation: OsPathManipulation) -> Vec<String> {
+    let separator = list_separator(manipulation);
+    let windows = manipulation == OsPathManipulation::Windows;
+    let mut r = Vec::<String>::new();
+    let mut entry = String::new();
+    let mut quoted = false;
+    for ch in list.chars() {
+        if windows && ch == '"' {
+            quoted = !quoted;
+        } else if ch == separator && !quoted {
+            if !entry.is_empty() {
+                r.push(std::mem::take(&mut entry));
+            }
+        } else {
+            entry.push(ch);
+        }
+    }
+    if !entry.is_empty() {
+        r.push(entry);
+    }
+    r
+}
+
+/// Joins paths into a path list, the reverse of [`split_path_list`].
+/// Empty paths are skipped. For the `Windows` manipulation, a path
+/// containing `;` is enclosed in double quotes.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("/a:/b", os_based::join_path_list(["/a", "/b"], OsPathManipulation::Common));
+/// ```
+pub fn join_path_list<'a, I: IntoIterator<Item = &'a str>>(paths: I, manipulation: OsPathManipulation) -> String {
+    let separator = list_separator(manipulation);
+    let entries: Vec<String> = paths.into_iter().filter(|p| !p.is_empty()).map(|p| {
+        if manipulation == OsPathManipulation::Windows && p.contains(separator) {
+            format!("\"{}\"", p)
+        } else {
+            p.to_owned()
+        }
+    }).collect();
+    entries.join(&separator.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_lists() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(vec![r"C:\a", r"C:\b"], split_path_list(r"C:\a;C:\b", windows));
+        assert_eq!(vec![r"C:\a", r"C:\x;y", r"D:\"], split_path_list(r#";C:\a;;"C:\x;y";D:\;"#, windows));
+        assert_eq!(r#"C:\a;"C:\x;y""#, join_path_list([r"C:\a", "", r"C:\x;y"], windows));
+        let list = join_path_list([r"C:\a", r"C:\x;y"], windows);
+        assert_eq!(vec![r"C:\a", r"C:\x;y"], split_path_list(&list, windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!(vec!["/a", "/b"], split_path_list("/a:/b", common));
+        assert_eq!(vec!["/usr/bin", "\"/x\""], split_path_list("::/usr/bin:\"/x\":", common));
+        assert_eq!("/a:/b", join_path_list(["/a", "/b"], common));
+        assert!(split_path_list("", common).is_empty());
+    }
+}
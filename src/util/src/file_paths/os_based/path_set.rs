@@ -0,0 +1,87 @@
+use super::*;
+use crate::collections::Set;
+
+/// The `PathSet` structure is a set of paths that compares paths by
+/// [`equality_key`], so that for the `Windows` manipulation paths that
+/// differ only in case or in separators are the same entry. Each entry
+/// keeps the path as it was first inserted, and entries are iterated
+/// in insertion order.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{OsPathManipulation, PathSet};
+/// let mut set = PathSet::new(OsPathManipulation::Windows);
+/// assert!(set.insert("C:/Foo"));
+/// assert!(!set.insert(r"c:\foo"));
+/// assert_eq!(vec!["C:/Foo"], set.iter().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathSet {
+    keys: Set<String>,
+    paths: Vec<String>,
+    manipulation: OsPathManipulation,
+}
+
+impl PathSet {
+    /// Constructs an empty `PathSet`.
+    pub fn new(manipulation: OsPathManipulation) -> Self {
+        Self { keys: Set::new(), paths: vec![], manipulation }
+    }
+
+    /// Inserts a path, returning `false` if an equal path is
+    /// already present, in which case the set is unchanged.
+    pub fn insert(&mut self, path: &str) -> bool {
+        let inserted = self.keys.insert(equality_key(path, self.manipulation));
+        if inserted {
+            self.paths.push(path.to_owned());
+        }
+        inserted
+    }
+
+    /// Indicates whether a path equal to the given path is present.
+    pub fn contains(&self, path: &str) -> bool {
+        self.keys.contains(&equality_key(path, self.manipulation))
+    }
+
+    /// Iterates over the paths as they were first inserted,
+    /// in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.paths.iter().map(|p| p.as_str())
+    }
+
+    /// Returns the number of paths.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Indicates whether the set has no paths.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_sets() {
+        let mut set = PathSet::new(OsPathManipulation::Windows);
+        assert!(set.is_empty());
+        assert!(set.insert("C:/Foo"));
+        assert!(!set.insert("c:/foo"));
+        assert!(!set.insert(r"C:\FOO\bar\.."));
+        assert!(set.insert("C:/Foo/x"));
+        assert_eq!(vec!["C:/Foo", "C:/Foo/x"], set.iter().collect::<Vec<_>>());
+        assert_eq!(2, set.len());
+        assert!(set.contains(r"c:\foo\"));
+        assert!(!set.contains("D:/Foo"));
+
+        let mut set = PathSet::new(OsPathManipulation::Common);
+        assert!(set.insert("/a/B"));
+        assert!(set.insert("/a/b"));
+        assert!(!set.insert("/a//b/"));
+        assert_eq!(vec!["/a/B", "/a/b"], set.iter().collect::<Vec<_>>());
+    }
+}
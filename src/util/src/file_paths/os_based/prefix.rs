@@ -0,0 +1,139 @@
+use super::*;
+
+/// The `PrefixMatcher` structure recognizes additional path prefixes,
+/// such as custom schemes, besides the ones built into a manipulation.
+/// It is constructed by [`prefix_matcher`].
+#[derive(Clone, Debug)]
+pub struct PrefixMatcher {
+    reg_exp: Option<RegExp>,
+}
+
+/// Builds a [`PrefixMatcher`] recognizing any of the given literal
+/// prefixes. When several prefixes match, the longest one is used.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let matcher = os_based::prefix_matcher(&["mem:"]);
+/// assert_eq!(Some(("mem:", "/a")), matcher.strip_prefix("mem:/a"));
+/// assert_eq!("mem:/b", os_based::resolve_with_matcher("mem:/a", "../b", OsPathManipulation::Common, Some(&matcher)));
+/// ```
+pub fn prefix_matcher(extra_prefixes: &[&str]) -> PrefixMatcher {
+    let mut prefixes: Vec<&str> = extra_prefixes.iter().copied().filter(|p| !p.is_empty()).collect();
+    if prefixes.is_empty() {
+        return PrefixMatcher { reg_exp: None };
+    }
+    prefixes.sort_by_key(|p| std::cmp::Reverse(p.len()));
+    let alternatives: Vec<String> = prefixes.into_iter().map(reg_exp::escape).collect();
+    let reg_exp = RegExp::new(&format!("^(?:{})", alternatives.join("|"))).unwrap();
+    PrefixMatcher { reg_exp: Some(reg_exp) }
+}
+
+impl PrefixMatcher {
+    /// Indicates whether a path starts with one of the prefixes.
+    pub fn is_prefixed(&self, path: &str) -> bool {
+        self.strip_prefix(path).is_some()
+    }
+
+    /// Splits a path into the prefix it starts with and the rest,
+    /// or returns `None` if it starts with none of the prefixes.
+    pub fn strip_prefix<'a>(&self, path: &'a str) -> Option<(&'a str, &'a str)> {
+        let m = self.reg_exp.as_ref()?.find(path)?;
+        Some(path.split_at(m.end()))
+    }
+}
+
+/// Indicates whether a path is absolute as [`is_absolute`] does,
+/// additionally considering paths that start with a prefix recognized
+/// by `matcher` as absolute.
+pub fn is_absolute_with_matcher(path: &str, manipulation: OsPathManipulation, matcher: Option<&PrefixMatcher>) -> bool {
+    matcher.is_some_and(|m| m.is_prefixed(path)) || is_absolute(path, manipulation)
+}
+
+/// Resolves `path2` relative to `path1` as [`resolve`] does, additionally
+/// preserving a prefix recognized by `matcher`. The rest of a prefixed
+/// path is always rooted, so that `..` portions cannot remove the prefix
+/// and `mem:a` resolves to `mem:/a`.
+pub fn resolve_with_matcher(path1: &str, path2: &str, manipulation: OsPathManipulation, matcher: Option<&PrefixMatcher>) -> String {
+    let Some(matcher) = matcher else {
+        return resolve(path1, path2, manipulation);
+    };
+    if let Some((prefix, rest)) = matcher.strip_prefix(path2) {
+        return prefix.to_owned() + &resolve("/", rest, manipulation);
+    }
+    if is_absolute(path2, manipulation) {
+        return resolve_one(path2, manipulation);
+    }
+    match matcher.strip_prefix(path1) {
+        Some((prefix, rest)) => prefix.to_owned() + &resolve_n(["/", rest, path2], manipulation),
+        None => resolve(path1, path2, manipulation),
+    }
+}
+
+/// Resolves `path2` relative to `path1` as [`resolve`] does, except that a
+/// leading `//` is not collapsed into `/`, for virtual file systems where
+/// it introduces a namespace such as in `//authority/path`.
+///
+/// The leading `//` is kept if `path2` starts with it or, when `path2`
+/// is relative, if `path1` starts with it. Three or more leading slashes
+/// are collapsed as usual.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("//authority/a/c", os_based::resolve_keep_double_slash("//authority/a/b", "../c", OsPathManipulation::Common));
+/// assert_eq!("/authority/a/c", os_based::resolve("//authority/a/b", "../c", OsPathManipulation::Common));
+/// ```
+pub fn resolve_keep_double_slash(path1: &str, path2: &str, manipulation: OsPathManipulation) -> String {
+    let starts_with_double_slash = |p: &str| p.starts_with("//") && !p.starts_with("///");
+    let base = if is_absolute(path2, manipulation) { path2 } else { path1 };
+    let r = resolve(path1, path2, manipulation);
+    if starts_with_double_slash(base) && r.starts_with('/') { "/".to_owned() + &r } else { r }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn double_slash() {
+        let common = OsPathManipulation::Common;
+        assert_eq!("//authority/a/c", resolve_keep_double_slash("//authority/a/b", "../c", common));
+        assert_eq!("//authority/x", resolve_keep_double_slash("/a", "//authority/./x/", common));
+        assert_eq!("//authority/a/b", resolve_keep_double_slash("//authority/a", "b", common));
+        assert_eq!("//", resolve_keep_double_slash("//authority", "..", common));
+        assert_eq!("/x", resolve_keep_double_slash("//authority/a", "/x", common));
+        assert_eq!("/a/b", resolve_keep_double_slash("///a", "b", common));
+        assert_eq!("a/b", resolve_keep_double_slash("a", "b", common));
+        assert_eq!(resolve("/a//b", "../c", common), resolve_keep_double_slash("/a//b", "../c", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("//authority/a", resolve_keep_double_slash("//authority", "a", windows));
+        assert_eq!("C:/a", resolve_keep_double_slash("//authority", "C:/a", windows));
+    }
+
+    #[test]
+    fn custom_prefixes() {
+        let common = OsPathManipulation::Common;
+        let matcher = prefix_matcher(&["mem:", "mem:x:"]);
+        assert!(matcher.is_prefixed("mem:/a"));
+        assert!(!matcher.is_prefixed("/mem:/a"));
+        assert_eq!(Some(("mem:x:", "/a")), matcher.strip_prefix("mem:x:/a"));
+        assert!(is_absolute_with_matcher("mem:a", common, Some(&matcher)));
+        assert!(!is_absolute_with_matcher("mem:a", common, None));
+        assert!(is_absolute_with_matcher("/a", common, Some(&matcher)));
+
+        assert_eq!("mem:/a/c", resolve_with_matcher("mem:/a/b", "../c", common, Some(&matcher)));
+        assert_eq!("mem:/c", resolve_with_matcher("mem:/a", "../../../c", common, Some(&matcher)));
+        assert_eq!("mem:/a", resolve_with_matcher("/x", "mem:a", common, Some(&matcher)));
+        assert_eq!("/y", resolve_with_matcher("mem:/a", "/y", common, Some(&matcher)));
+        assert_eq!("/x/y", resolve_with_matcher("/x", "y", common, Some(&matcher)));
+        assert_eq!("mem:/a/c", resolve_with_matcher("mem:/a", "c", common, None));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("C:/a", resolve_with_matcher("mem:/x", r"C:\a", windows, Some(&matcher)));
+        assert!(!prefix_matcher(&[]).is_prefixed("mem:/a"));
+    }
+}
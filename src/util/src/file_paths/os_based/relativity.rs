@@ -0,0 +1,172 @@
+use super::*;
+
+/// Replaces the base `old_base` of a path by `new_base`.
+///
+/// Returns `None` if `path` is not `old_base` or inside it.
+/// All paths are resolved before comparison and, for the `Windows`
+/// manipulation, portions are compared case-insensitively.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(Some("D:/x/b/c".to_owned()), os_based::rebase("C:/a/b/c", "C:/a", "D:/x", OsPathManipulation::Windows));
+/// ```
+pub fn rebase(path: &str, old_base: &str, new_base: &str, manipulation: OsPathManipulation) -> Option<String> {
+    let (prefix, segments) = split_prefix(path, manipulation);
+    let (old_prefix, old_segments) = split_prefix(old_base, manipulation);
+    if !portion_eq(&prefix, &old_prefix, manipulation) || segments.len() < old_segments.len() {
+        return None;
+    }
+    if !segments.iter().zip(old_segments.iter()).all(|(a, b)| portion_eq(a, b, manipulation)) {
+        return None;
+    }
+    Some(resolve(new_base, &segments[old_segments.len()..].join("/"), manipulation))
+}
+
+/// Finds the relative path from `from_path` to each of the targets,
+/// parsing `from_path` only once. Each result is the same as the one
+/// returned by [`relative`].
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!(vec!["b", "../c"], os_based::relative_many("/a", ["/a/b", "/c"], OsPathManipulation::Common));
+/// ```
+///
+/// # Panics
+///
+/// Panics if given paths are not absolute.
+pub fn relative_many<'a, I: IntoIterator<Item = &'a str>>(from_path: &str, targets: I, manipulation: OsPathManipulation) -> Vec<String> {
+    let from_path = Path::new(from_path, manipulation);
+    targets.into_iter().map(|target| from_path.relative(target)).collect()
+}
+
+/// Options for [`relative_opts`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RelativeOptions {
+    /// Whether `.` is returned instead of an empty string
+    /// when both paths refer to the same path.
+    pub empty_as_dot: bool,
+}
+
+/// Finds the relative path from `from_path` to `to_path` as [`relative`]
+/// does, with behavior adjusted by `opts`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation, RelativeOptions};
+/// let opts = RelativeOptions { empty_as_dot: true };
+/// assert_eq!(".", os_based::relative_opts("C:/foo", "C:/foo", OsPathManipulation::Windows, opts));
+/// ```
+///
+/// # Panics
+///
+/// Panics if given paths are not absolute.
+pub fn relative_opts(from_path: &str, to_path: &str, manipulation: OsPathManipulation, opts: RelativeOptions) -> String {
+    let r = relative(from_path, to_path, manipulation);
+    if r.is_empty() && opts.empty_as_dot { ".".to_owned() } else { r }
+}
+
+/// Simplifies a relative path against a base that is `base_depth`
+/// directories deep, without resolving the base itself.
+///
+/// `..` portions cancel the preceding portions, and the remaining
+/// leading `..` portions are kept, up to `base_depth` of them. Since
+/// the parent of a root is the root itself, further `..` portions are
+/// dropped. `.` and empty portions are eliminated. An absolute path
+/// is resolved as [`resolve_one`] does.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("../x", os_based::simplify_relative("a/b/c/../../../../x", 3, OsPathManipulation::Common));
+/// assert_eq!("x", os_based::simplify_relative("a/b/c/../../../../x", 0, OsPathManipulation::Common));
+/// ```
+pub fn simplify_relative(path: &str, base_depth: usize, manipulation: OsPathManipulation) -> String {
+    if is_absolute(path, manipulation) {
+        return resolve_one(path, manipulation);
+    }
+    let mut parents = 0usize;
+    let mut segments = Vec::<&str>::new();
+    for portion in path.split(['/', '\\']) {
+        match portion {
+            "" | "." => {},
+            ".." => if segments.pop().is_none() && parents < base_depth {
+                parents += 1;
+            },
+            _ => segments.push(portion),
+        }
+    }
+    let mut r = vec![".."; parents];
+    r.extend(segments);
+    r.join("/")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rebasing() {
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Some("D:/x/b/c".to_owned()), rebase("C:/a/b/c", "C:/a", "D:/x", windows));
+        assert_eq!(Some("D:/x/b/c".to_owned()), rebase(r"c:\A\b\c", "C:/a/", "D:/x", windows));
+        assert_eq!(Some("D:/x".to_owned()), rebase("C:/a", "C:/a", "D:/x", windows));
+        assert_eq!(None, rebase("C:/ab/c", "C:/a", "D:/x", windows));
+        assert_eq!(None, rebase("D:/a/b", "C:/a", "D:/x", windows));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!(Some("/z/b".to_owned()), rebase("/a/./b", "/a", "/z", common));
+        assert_eq!(None, rebase("/A/b", "/a", "/z", common));
+    }
+
+    #[test]
+    fn relative_with_options() {
+        let windows = OsPathManipulation::Windows;
+        let dot = RelativeOptions { empty_as_dot: true };
+        assert_eq!("", relative_opts("C:/foo", "C:/foo", windows, RelativeOptions::default()));
+        assert_eq!(".", relative_opts("C:/foo", "C:/foo", windows, dot));
+        assert_eq!(".", relative_opts("C:/foo/", r"C:\foo", windows, dot));
+        assert_eq!("../bar", relative_opts("C:/foo", "C:/bar", windows, dot));
+
+        let common = OsPathManipulation::Common;
+        assert_eq!(".", relative_opts("/a/./b", "/a/b", common, dot));
+        assert_eq!("c", relative_opts("/a/b", "/a/b/c", common, dot));
+    }
+
+    #[test]
+    fn simplification() {
+        let common = OsPathManipulation::Common;
+        let path = "a/b/c/../../../../x";
+        assert_eq!("../x", simplify_relative(path, 1, common));
+        assert_eq!("../x", simplify_relative(path, 5, common));
+        assert_eq!("x", simplify_relative(path, 0, common));
+        assert_eq!("../../y", simplify_relative("../../../y", 2, common));
+        assert_eq!("../../a/c", simplify_relative("../.././a//b/../c/", 4, common));
+        assert_eq!("", simplify_relative("a/..", 1, common));
+        assert_eq!("/x", simplify_relative("/a/../x", 1, common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("../b", simplify_relative(r"a\..\..\b", 1, windows));
+        assert_eq!("C:/b", simplify_relative(r"C:\a\..\b", 1, windows));
+    }
+
+    #[test]
+    fn relative_to_many() {
+        let windows = OsPathManipulation::Windows;
+        let targets = ["C:/a/b/c", "C:/a", "C:/x/y", "D:/z", r"c:\A\b"];
+        let expected: Vec<String> = targets.iter().map(|t| relative("C:/a/b", t, windows)).collect();
+        assert_eq!(expected, relative_many("C:/a/b", targets, windows));
+        assert_eq!("D:/z", relative_many("C:/a/b", ["D:/z"], windows)[0]);
+
+        let common = OsPathManipulation::Common;
+        let targets = ["/a/b", "/a/b/c/d", "/", "/q"];
+        let expected: Vec<String> = targets.iter().map(|t| relative("/a/b", t, common)).collect();
+        assert_eq!(expected, relative_many("/a/b", targets, common));
+        assert!(relative_many("/a", [], common).is_empty());
+    }
+}
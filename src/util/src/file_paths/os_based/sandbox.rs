@@ -0,0 +1,178 @@
+use super::*;
+
+/// The `EscapeError` structure represents an error that occurred
+/// because a path refers to a location outside a virtual root.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EscapeError {
+    /// The path that escapes the root, as given.
+    pub path: String,
+}
+
+impl std::fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Path {:?} escapes the virtual root", self.path)
+    }
+}
+
+impl std::error::Error for EscapeError {}
+
+/// The `SandboxError` enumeration represents an error that occurred
+/// because a path is not inside any allowed root.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SandboxError {
+    /// No roots were given.
+    NoRoots,
+    /// The resolved path is outside all of the roots.
+    OutsideRoots {
+        path: String,
+    },
+}
+
+impl std::fmt::Display for SandboxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoRoots => write!(f, "No allowed roots were given"),
+            Self::OutsideRoots { path } => write!(f, "Path {:?} is outside all of the allowed roots", path),
+        }
+    }
+}
+
+impl std::error::Error for SandboxError {}
+
+/// Resolves a path and verifies that the result is one of `roots` or
+/// inside one of them, returning the index of the first matching root
+/// along with the resolved path.
+///
+/// A relative path is resolved against the first root. Paths are compared
+/// by portions, case-insensitively for the `Windows` manipulation, so that
+/// `/data2` is not inside `/data`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// let roots = ["/mnt/a", "/mnt/b"];
+/// assert_eq!(Ok((1, "/mnt/b/x".to_owned())), os_based::resolve_within_roots(&roots, "/mnt/b/x", OsPathManipulation::Common));
+/// assert!(os_based::resolve_within_roots(&roots, "/etc/passwd", OsPathManipulation::Common).is_err());
+/// ```
+pub fn resolve_within_roots(roots: &[&str], rel_or_abs: &str, manipulation: OsPathManipulation) -> Result<(usize, String), SandboxError> {
+    let first = roots.first().ok_or(SandboxError::NoRoots)?;
+    let path = resolve(first, rel_or_abs, manipulation);
+    let (prefix, segments) = split_prefix(&path, manipulation);
+    let inside = |root: &str| {
+        let (root_prefix, root_segments) = split_prefix(root, manipulation);
+        portion_eq(&prefix, &root_prefix, manipulation)
+            && segments.len() >= root_segments.len()
+            && segments.iter().zip(root_segments.iter()).all(|(a, b)| portion_eq(a, b, manipulation))
+    };
+    match roots.iter().position(|root| inside(root)) {
+        Some(index) => Ok((index, path)),
+        None => Err(SandboxError::OutsideRoots { path }),
+    }
+}
+
+/// The `Chroot` structure confines paths to a virtual root.
+///
+/// Paths given to a `Chroot` are interpreted inside the virtual root:
+/// `/foo` and `foo` both refer to `<root>/foo`, and a `..` portion
+/// that would leave the root is rejected.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{Chroot, OsPathManipulation};
+/// let chroot = Chroot::new("/srv/plugin", OsPathManipulation::Common);
+/// assert_eq!(Ok("data/a.txt".to_owned()), chroot.resolve("/data/./a.txt"));
+/// assert_eq!(Ok("/srv/plugin/data/a.txt".to_owned()), chroot.resolve_real("data/a.txt"));
+/// assert!(chroot.resolve("../../etc/passwd").is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Chroot {
+    root: String,
+    manipulation: OsPathManipulation,
+}
+
+impl Chroot {
+    /// Constructs a `Chroot`, resolving the given root.
+    pub fn new(root: &str, manipulation: OsPathManipulation) -> Self {
+        Self { root: resolve_one(root, manipulation), manipulation }
+    }
+
+    /// Returns the resolved root.
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Resolves a path inside the virtual root, returning it relative
+    /// to the virtual root. An empty string refers to the root itself.
+    ///
+    /// Fails if the path leaves the root through `..` portions or,
+    /// for the `Windows` manipulation, if it has a drive or UNC prefix.
+    pub fn resolve(&self, path: &str) -> Result<String, EscapeError> {
+        let escape = || EscapeError { path: path.to_owned() };
+        if self.manipulation == OsPathManipulation::Windows && reg_exp_is_match!(r"^([A-Za-z]:|[/\\]{2})", path) {
+            return Err(escape());
+        }
+        let mut segments = Vec::<&str>::new();
+        for segment in path.split(['/', '\\']) {
+            match segment {
+                "" | "." => {},
+                ".." => {
+                    segments.pop().ok_or_else(escape)?;
+                },
+                _ => segments.push(segment),
+            }
+        }
+        Ok(segments.join("/"))
+    }
+
+    /// Resolves a path inside the virtual root as [`Chroot::resolve`]
+    /// does, returning the path joined to the actual root.
+    pub fn resolve_real(&self, path: &str) -> Result<String, EscapeError> {
+        Ok(resolve(&self.root, &self.resolve(path)?, self.manipulation))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn within_roots() {
+        let common = OsPathManipulation::Common;
+        let roots = ["/mnt/data", "/mnt/media/"];
+        assert_eq!(Ok((0, "/mnt/data/a".to_owned())), resolve_within_roots(&roots, "a", common));
+        assert_eq!(Ok((1, "/mnt/media/x/y".to_owned())), resolve_within_roots(&roots, "/mnt/media/x/./y", common));
+        assert_eq!(Ok((1, "/mnt/media/z".to_owned())), resolve_within_roots(&roots, "../media/z", common));
+        assert_eq!(Ok((0, "/mnt/data".to_owned())), resolve_within_roots(&roots, "/mnt/data", common));
+        assert_eq!(Err(SandboxError::OutsideRoots { path: "/mnt/data2".into() }), resolve_within_roots(&roots, "/mnt/data2", common));
+        assert_eq!(Err(SandboxError::OutsideRoots { path: "/etc".into() }), resolve_within_roots(&roots, "../../etc", common));
+        assert_eq!(Err(SandboxError::NoRoots), resolve_within_roots(&[], "/a", common));
+
+        let windows = OsPathManipulation::Windows;
+        let roots = [r"C:\Volumes\A", "D:/"];
+        assert_eq!(Ok((0, "c:/volumes/a/f".to_owned())), resolve_within_roots(&roots, "c:/volumes/a/f", windows));
+        assert_eq!(Ok((1, "D:/x".to_owned())), resolve_within_roots(&roots, r"D:\x", windows));
+        assert!(resolve_within_roots(&roots, "E:/x", windows).is_err());
+    }
+
+    #[test]
+    fn chroot() {
+        let chroot = Chroot::new("/srv/./plugin/", OsPathManipulation::Common);
+        assert_eq!("/srv/plugin", chroot.root());
+        assert_eq!(Ok("foo/bar".to_owned()), chroot.resolve("/foo//bar/"));
+        assert_eq!(Ok("bar".to_owned()), chroot.resolve("foo/../bar"));
+        assert_eq!(Ok("".to_owned()), chroot.resolve("/"));
+        assert_eq!(Ok("/srv/plugin/foo".to_owned()), chroot.resolve_real("/foo"));
+        assert_eq!(Ok("/srv/plugin".to_owned()), chroot.resolve_real(""));
+        assert_eq!(Err(EscapeError { path: "../../etc".into() }), chroot.resolve("../../etc"));
+        assert!(chroot.resolve("a/../../etc").is_err());
+        assert!(chroot.resolve_real("/..").is_err());
+
+        let chroot = Chroot::new(r"C:\plugins\x", OsPathManipulation::Windows);
+        assert_eq!(Ok("C:/plugins/x/a/b".to_owned()), chroot.resolve_real(r"\a\b"));
+        assert!(chroot.resolve("D:/a").is_err());
+        assert!(chroot.resolve(r"\\server\share").is_err());
+        assert!(chroot.resolve(r"a\..\..\x").is_err());
+    }
+}
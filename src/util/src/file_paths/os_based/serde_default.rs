@@ -0,0 +1,34 @@
+/*!
+Serialize and deserialize path fields with the `Common` manipulation.
+
+Use this module through the `#[serde(with = "...")]` attribute. Paths
+are resolved when deserialized and serialized as they are.
+
+# Example
+
+```
+use rialight_util::serialization::{*, json};
+
+#[derive(Serialize, Deserialize)]
+struct Config {
+    #[serde(with = "rialight_util::file_paths::os_based::serde_default")]
+    path: String,
+}
+
+let config: Config = json::deserialize(r#"{"path": "/a/./b/"}"#).unwrap();
+assert_eq!("/a/b", config.path);
+```
+*/
+
+use crate::serialization::{Deserialize, Deserializer, Serializer};
+use super::{resolve_one, OsPathManipulation};
+
+/// Serializes a path field.
+pub fn serialize<S: Serializer>(path: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(path)
+}
+
+/// Deserializes a path field, resolving it with the `Common` manipulation.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(resolve_one(&String::deserialize(deserializer)?, OsPathManipulation::Common))
+}
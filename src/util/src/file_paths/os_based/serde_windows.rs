@@ -0,0 +1,52 @@
+/*!
+Serialize and deserialize path fields with the `Windows` manipulation.
+
+Use this module through the `#[serde(with = "...")]` attribute. Paths
+are resolved when deserialized and serialized as they are.
+
+# Example
+
+```
+use rialight_util::serialization::{*, json};
+
+#[derive(Serialize, Deserialize)]
+struct Config {
+    #[serde(with = "rialight_util::file_paths::os_based::serde_windows")]
+    path: String,
+}
+
+let config: Config = json::deserialize(r#"{"path": "C:\\a\\.\\b"}"#).unwrap();
+assert_eq!("C:/a/b", config.path);
+```
+*/
+
+use crate::serialization::{Deserialize, Deserializer, Serializer};
+use super::{resolve_one, OsPathManipulation};
+
+/// Serializes a path field.
+pub fn serialize<S: Serializer>(path: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(path)
+}
+
+/// Deserializes a path field, resolving it with the `Windows` manipulation.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(resolve_one(&String::deserialize(deserializer)?, OsPathManipulation::Windows))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::serialization::{*, json};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Entry {
+        #[serde(with = "super")]
+        path: String,
+    }
+
+    #[test]
+    fn path_field() {
+        let entry: Entry = json::deserialize(r#"{"path": "c:\\a\\..\\b\\"}"#).unwrap();
+        assert_eq!(Entry { path: "c:/b".into() }, entry);
+        assert_eq!(r#"{"path":"c:/b"}"#, json::serialize(&entry).unwrap());
+    }
+}
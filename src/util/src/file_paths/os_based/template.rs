@@ -0,0 +1,57 @@
+use super::*;
+
+/// Generates a path from a template, filling placeholders from the parts
+/// of a source path:
+///
+/// - `{dir}` is the directory of the source path, or `.` if it has none.
+/// - `{name}` is the base name of the source path, such as `photo.png`.
+/// - `{stem}` is the base name without its last extension, such as `photo`.
+/// - `{ext}` is the last extension without the dot, such as `png`.
+///
+/// Unknown placeholders are left literal. If the source path has no
+/// extension, a `.{ext}` sequence is removed entirely. The generated
+/// path is resolved according to `manipulation`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert_eq!("a/b/photo-thumb.png", os_based::interpolate_template("a/b/photo.png", "{dir}/{stem}-thumb.{ext}", OsPathManipulation::Common));
+/// ```
+pub fn interpolate_template(path: &str, template: &str, manipulation: OsPathManipulation) -> String {
+    let (prefix, mut segments) = split_prefix(path, manipulation);
+    let name = segments.pop().unwrap_or_default();
+    let dir = prefix + &segments.join("/");
+    let dir = if dir.is_empty() { ".".to_owned() } else { dir };
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem.to_owned(), ext.to_owned()),
+        _ => (name.clone(), "".to_owned()),
+    };
+    let r = reg_exp_replace_all!(r"(\.?)\{(dir|stem|ext|name)\}", template, |_, dot: &str, placeholder: &str| {
+        match placeholder {
+            "dir" => dot.to_owned() + &dir,
+            "stem" => dot.to_owned() + &stem,
+            "name" => dot.to_owned() + &name,
+            _ => if ext.is_empty() { "".to_owned() } else { dot.to_owned() + &ext },
+        }
+    });
+    resolve_one(&r, manipulation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn templates() {
+        let common = OsPathManipulation::Common;
+        assert_eq!("/a/b/photo-thumb.png", interpolate_template("/a/b/photo.png", "{dir}/{stem}-thumb.{ext}", common));
+        assert_eq!("out/photo.png.bak", interpolate_template("photo.png", "out/{name}.bak", common));
+        assert_eq!("photo-thumb", interpolate_template("photo", "{dir}/{stem}-thumb.{ext}", common));
+        assert_eq!("a/.bashrc.{unknown}", interpolate_template("a/.bashrc", "{dir}/{name}.{unknown}", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("C:/photos/x.tar-thumb.gz", interpolate_template(r"C:\photos\x.tar.gz", r"{dir}\{stem}-thumb.{ext}", windows));
+        assert_eq!("C:/x-thumb.jpg", interpolate_template("C:/x.jpg", "{dir}/{stem}-thumb.{ext}", windows));
+    }
+}
@@ -0,0 +1,147 @@
+use super::*;
+
+/// The `PathKindError` enumeration represents an error that
+/// occurred because a path is not of the expected kind.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PathKindError {
+    /// An absolute path was expected.
+    NotAbsolute {
+        path: String,
+    },
+    /// A relative path was expected.
+    NotRelative {
+        path: String,
+    },
+}
+
+impl std::fmt::Display for PathKindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAbsolute { path } => write!(f, "Path {:?} is not absolute", path),
+            Self::NotRelative { path } => write!(f, "Path {:?} is not relative", path),
+        }
+    }
+}
+
+impl std::error::Error for PathKindError {}
+
+/// The `AbsolutePath` structure represents a resolved path that
+/// is guaranteed to be absolute, based on a manipulation.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{AbsolutePath, RelativePath, OsPathManipulation};
+/// let windows = OsPathManipulation::Windows;
+/// let base = AbsolutePath::new(r"C:\a", windows).unwrap();
+/// let path = base.join(&RelativePath::new("b/c", windows).unwrap());
+/// assert_eq!("C:/a/b/c", path.as_str());
+/// assert!(AbsolutePath::new("b/c", windows).is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct AbsolutePath(String, OsPathManipulation);
+
+impl AbsolutePath {
+    /// Constructs an `AbsolutePath`, resolving the given path.
+    /// Returns an error if the path is not absolute.
+    pub fn new(path: &str, manipulation: OsPathManipulation) -> Result<Self, PathKindError> {
+        if !is_absolute(path, manipulation) {
+            return Err(PathKindError::NotAbsolute { path: path.to_owned() });
+        }
+        Ok(Self(resolve_one(path, manipulation), manipulation))
+    }
+
+    /// Returns the manipulation this path is based on.
+    pub fn manipulation(&self) -> OsPathManipulation {
+        self.1
+    }
+
+    /// Returns the path as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Resolves a relative path against this path.
+    pub fn join(&self, path: &RelativePath) -> AbsolutePath {
+        Self(resolve(&self.0, &path.0, self.1), self.1)
+    }
+
+    /// Finds the relative path from this path to `to_path`.
+    /// See [`relative`].
+    pub fn relative(&self, to_path: &AbsolutePath) -> String {
+        relative(&self.0, &to_path.0, self.1)
+    }
+}
+
+impl std::fmt::Display for AbsolutePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for AbsolutePath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The `RelativePath` structure represents a resolved path that
+/// is guaranteed to be relative, based on a manipulation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct RelativePath(String, OsPathManipulation);
+
+impl RelativePath {
+    /// Constructs a `RelativePath`, resolving the given path.
+    /// Returns an error if the path is not relative.
+    pub fn new(path: &str, manipulation: OsPathManipulation) -> Result<Self, PathKindError> {
+        if !is_relative(path, manipulation) {
+            return Err(PathKindError::NotRelative { path: path.to_owned() });
+        }
+        Ok(Self(resolve_one(path, manipulation), manipulation))
+    }
+
+    /// Returns the manipulation this path is based on.
+    pub fn manipulation(&self) -> OsPathManipulation {
+        self.1
+    }
+
+    /// Returns the path as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RelativePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for RelativePath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn typed_paths() {
+        let common = OsPathManipulation::Common;
+        let base = AbsolutePath::new("/a/./b/", common).unwrap();
+        assert_eq!("/a/b", base.as_str());
+        let rel = RelativePath::new("c/../d", common).unwrap();
+        assert_eq!("d", rel.as_str());
+        assert_eq!("/a/b/d", base.join(&rel).to_string());
+        assert_eq!("../c", base.relative(&AbsolutePath::new("/a/c", common).unwrap()));
+        assert_eq!(Err(PathKindError::NotAbsolute { path: "a".into() }), AbsolutePath::new("a", common));
+        assert_eq!(Err(PathKindError::NotRelative { path: "/a".into() }), RelativePath::new("/a", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert!(AbsolutePath::new(r"\\server\share", windows).is_ok());
+        assert!(RelativePath::new("C:/a", windows).is_err());
+        assert!(RelativePath::new("C:/a", common).is_ok());
+    }
+}
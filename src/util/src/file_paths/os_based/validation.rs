@@ -0,0 +1,128 @@
+use super::*;
+
+static WINDOWS_RESERVED_NAME: StaticRegExp = static_reg_exp!(r"(?i)^(CON|PRN|AUX|NUL|COM[1-9]|LPT[1-9])(\..*)?$");
+
+static WINDOWS_PATH_PREFIX: StaticRegExp = static_reg_exp!(r"^([/\\]{2}[?.][/\\])?([A-Za-z]:)?");
+
+/// The `PathCharError` structure represents an error that occurred
+/// because a path contains a character that is not allowed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PathCharError {
+    /// The disallowed character.
+    pub character: char,
+    /// The byte offset of the character in the path.
+    pub offset: usize,
+}
+
+impl std::fmt::Display for PathCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Path contains disallowed character {:?} at byte {}", self.character, self.offset)
+    }
+}
+
+impl std::error::Error for PathCharError {}
+
+/// Indicates whether a character is disallowed by [`validate_path_chars`]
+/// outside of a path prefix.
+fn is_disallowed_char(ch: char, windows: bool) -> bool {
+    ch == '\0' || (windows && (ch < ' ' || "<>:\"|?*".contains(ch)))
+}
+
+/// Checks that a path contains no dangerous or disallowed characters,
+/// reporting the first one found.
+///
+/// A null character is always disallowed. For the `Windows` manipulation,
+/// the other control characters below U+0020 and the reserved characters
+/// `< > : " | ? *` are disallowed too, except for the colon of a drive
+/// prefix, such as in `C:\`, and the `\\?\` and `\\.\` prefixes.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert!(os_based::validate_path_chars(r"C:\a\b.txt", OsPathManipulation::Windows).is_ok());
+/// assert!(os_based::validate_path_chars(r"C:\a:b", OsPathManipulation::Windows).is_err());
+/// assert!(os_based::validate_path_chars("/a:b", OsPathManipulation::Common).is_ok());
+/// ```
+pub fn validate_path_chars(path: &str, manipulation: OsPathManipulation) -> Result<(), PathCharError> {
+    let windows = manipulation == OsPathManipulation::Windows;
+    let start = if windows { WINDOWS_PATH_PREFIX.find(path).map_or(0, |m| m.end()) } else { 0 };
+    match path[start..].char_indices().find(|(_, ch)| is_disallowed_char(*ch, windows)) {
+        Some((offset, character)) => Err(PathCharError { character, offset: start + offset }),
+        None => Ok(()),
+    }
+}
+
+/// Indicates whether a string is a plain file name, that is, a single
+/// path portion with no path separators that is not `.` or `..` and
+/// contains none of the characters disallowed by [`validate_path_chars`].
+/// For the `Windows` manipulation, this rules out any colon, such as in
+/// `C:evil` or `a.txt:ads`, and reserved device names such as `CON` or
+/// `nul.txt` are not plain file names either.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+/// assert!(os_based::is_plain_file_name("report.pdf", OsPathManipulation::Common));
+/// assert!(!os_based::is_plain_file_name("../report.pdf", OsPathManipulation::Common));
+/// ```
+pub fn is_plain_file_name(name: &str, manipulation: OsPathManipulation) -> bool {
+    let windows = manipulation == OsPathManipulation::Windows;
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) || name.chars().any(|ch| is_disallowed_char(ch, windows)) {
+        return false;
+    }
+    !windows || !WINDOWS_RESERVED_NAME.is_match(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_chars() {
+        let common = OsPathManipulation::Common;
+        assert_eq!(Ok(()), validate_path_chars("/a:b/<c>?", common));
+        assert_eq!(Err(PathCharError { character: '\0', offset: 2 }), validate_path_chars("/a\0b", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!(Ok(()), validate_path_chars(r"C:\Users\a.txt", windows));
+        assert_eq!(Ok(()), validate_path_chars(r"\\?\C:\a", windows));
+        assert_eq!(Ok(()), validate_path_chars(r"\\server\share\a", windows));
+        assert_eq!(Ok(()), validate_path_chars("relative/file", windows));
+        assert_eq!(Err(PathCharError { character: ':', offset: 8 }), validate_path_chars(r"C:\dir\a:b", windows));
+        assert_eq!(Err(PathCharError { character: ':', offset: 5 }), validate_path_chars("dir/a:b", windows));
+        assert_eq!(Ok(()), validate_path_chars("a:b", windows), "drive-relative path");
+        assert_eq!(Err(PathCharError { character: '\0', offset: 1 }), validate_path_chars("a\0", windows));
+        assert_eq!(Err(PathCharError { character: '\t', offset: 1 }), validate_path_chars("a\tb", windows));
+        assert_eq!(Err(PathCharError { character: '?', offset: 4 }), validate_path_chars("C:/a?", windows));
+        assert!(validate_path_chars(r"\\?\C:\a|b", windows).is_err());
+    }
+
+    #[test]
+    fn plain_file_names() {
+        let common = OsPathManipulation::Common;
+        assert!(is_plain_file_name("report.pdf", common));
+        assert!(is_plain_file_name(".bashrc", common));
+        assert!(is_plain_file_name("CON", common));
+        assert!(!is_plain_file_name("a/b", common));
+        assert!(!is_plain_file_name(r"a\b", common));
+        assert!(!is_plain_file_name("..", common));
+        assert!(!is_plain_file_name(".", common));
+        assert!(!is_plain_file_name("", common));
+        assert!(is_plain_file_name("a.txt:ads", common));
+        assert!(!is_plain_file_name("a\0", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert!(is_plain_file_name("report.pdf", windows));
+        assert!(is_plain_file_name("console.log", windows));
+        assert!(!is_plain_file_name("CON", windows));
+        assert!(!is_plain_file_name("nul.txt", windows));
+        assert!(!is_plain_file_name("com1", windows));
+        assert!(!is_plain_file_name("..", windows));
+        assert!(!is_plain_file_name("C:evil", windows));
+        assert!(!is_plain_file_name("a.txt:ads", windows));
+        assert!(!is_plain_file_name("a?.txt", windows));
+        assert!(!is_plain_file_name("a\tb", windows));
+    }
+}
@@ -0,0 +1,104 @@
+/// Resolves `path2` relative to `path1` using `sep` as the only path
+/// separator, which is useful for virtual file systems that use separators
+/// such as `:` or `>`.
+///
+/// The behavior is the same as resolving paths with the `Common`
+/// manipulation, except that no other character is a path separator.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths;
+/// assert_eq!(":a:c", file_paths::resolve_with_separator(":a:b", "..:c", ':'));
+/// assert_eq!(":c", file_paths::resolve_with_separator(":a:b", ":c", ':'));
+/// ```
+pub fn resolve_with_separator(path1: &str, path2: &str, sep: char) -> String {
+    if path2.starts_with(sep) || path1.is_empty() {
+        return normalize_with_separator(path2, sep);
+    }
+    if path2.is_empty() {
+        return normalize_with_separator(path1, sep);
+    }
+    normalize_with_separator(&format!("{}{}{}", path1, sep, path2), sep)
+}
+
+/// Normalizes a path using `sep` as the only path separator, eliminating
+/// the portions `..` and `.`, empty portions and trailing separators.
+/// A leading separator is preserved.
+pub fn normalize_with_separator(path: &str, sep: char) -> String {
+    let mut r = Vec::<&str>::new();
+    for p in path.split(sep) {
+        if p == "." || p.is_empty() {
+            continue;
+        } else if p == ".." {
+            r.pop();
+        } else {
+            r.push(p);
+        }
+    }
+    let r = r.join(&sep.to_string());
+    if path.starts_with(sep) { sep.to_string() + &r } else { r }
+}
+
+/// The path separators used by a path string.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SeparatorStyle {
+    /// Only forward slashes (`/`).
+    ForwardOnly,
+    /// Only backslashes (`\`).
+    BackslashOnly,
+    /// Both forward slashes and backslashes.
+    Mixed,
+    /// No path separators.
+    None,
+}
+
+/// Indicates which path separators a path uses, such as for warning
+/// about inconsistent path strings before they are normalized.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::{self, SeparatorStyle};
+/// assert_eq!(SeparatorStyle::Mixed, file_paths::separator_style(r"a/b\c"));
+/// ```
+pub fn separator_style(path: &str) -> SeparatorStyle {
+    match (path.contains('/'), path.contains('\\')) {
+        (true, true) => SeparatorStyle::Mixed,
+        (true, false) => SeparatorStyle::ForwardOnly,
+        (false, true) => SeparatorStyle::BackslashOnly,
+        (false, false) => SeparatorStyle::None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::os_based;
+
+    #[test]
+    fn separator_styles() {
+        assert_eq!(SeparatorStyle::ForwardOnly, separator_style("a/b"));
+        assert_eq!(SeparatorStyle::BackslashOnly, separator_style(r"a\b"));
+        assert_eq!(SeparatorStyle::Mixed, separator_style(r"a/b\c"));
+        assert_eq!(SeparatorStyle::None, separator_style("abc"));
+        assert_eq!(SeparatorStyle::None, separator_style(""));
+        assert_eq!(SeparatorStyle::BackslashOnly, separator_style(r"\\server\share"));
+    }
+
+    #[test]
+    fn custom_separator() {
+        assert_eq!("a:c", resolve_with_separator("a:b", "..:c", ':'));
+        assert_eq!(":x:y", resolve_with_separator("a:b", ":x::y:", ':'));
+        assert_eq!("a:b", resolve_with_separator("a:b", "", ':'));
+        assert_eq!("a/b:c", resolve_with_separator("a/b", "c", ':'));
+        assert_eq!("a>c", normalize_with_separator("a>.>b>..>c>", '>'));
+        assert_eq!(":", normalize_with_separator(":a:..", ':'));
+        assert_eq!("a.b", normalize_with_separator("a.b", '.'));
+
+        let common = os_based::OsPathManipulation::Common;
+        for (path1, path2) in [("/a/b", "../c"), ("a", "/x//y/"), ("a/b", ""), ("", "a"), ("/", ".."), ("a/./b", "c/..")] {
+            assert_eq!(os_based::resolve(path1, path2, common), resolve_with_separator(path1, path2, '/'));
+        }
+    }
+}
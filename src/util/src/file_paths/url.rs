@@ -0,0 +1,123 @@
+/*!
+Work with the path component of URLs.
+
+URL paths always use forward slashes and, unlike file paths, a trailing
+slash is significant, since it indicates that the last portion is a
+directory.
+
+# Example
+
+```
+use rialight_util::file_paths::url;
+
+assert_eq!("/page", url::resolve_url_path("/section/index.html?x=1", "../page"));
+```
+*/
+
+use super::os_based::{self, OsPathManipulation};
+use crate::reg_exp::*;
+use crate::uri::encode_uri_component;
+
+/// Resolves a relative URL path against a base URL path, as a browser
+/// resolves an `href`. Any query or fragment of `base` is removed before
+/// resolving, while a query or fragment of `rel` is kept.
+///
+/// The relative path is resolved against the directory of `base` (the
+/// portion up to its last `/`), and `.` and `..` portions are removed
+/// as described in RFC 3986. A `..` portion never leaves the root.
+pub fn resolve_url_path(base: &str, rel: &str) -> String {
+    let (rel_path, rel_suffix) = split_suffix(rel);
+    let (base_path, _) = split_suffix(base);
+    let merged = if rel_path.is_empty() {
+        base_path.to_owned()
+    } else if rel_path.starts_with('/') {
+        rel_path.to_owned()
+    } else {
+        match base_path.rfind('/') {
+            Some(i) => base_path[..=i].to_owned() + rel_path,
+            None => "/".to_owned() + rel_path,
+        }
+    };
+    remove_dot_segments(&merged) + rel_suffix
+}
+
+/// Finds the relative path from `from_path` to `to_path` as
+/// [`os_based::relative`](super::os_based::relative) does, and returns it
+/// as a URL path for a hyperlink: path separators become forward slashes
+/// and each portion is percent-encoded, such as `my%20file.html`. A
+/// drive prefix, which is returned when the paths are on different
+/// drives, is not encoded.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::file_paths::{os_based::OsPathManipulation, url};
+/// assert_eq!("../my%20docs/a.html", url::relative_url("/site/pages", "/site/my docs/a.html", OsPathManipulation::Common));
+/// ```
+///
+/// # Panics
+///
+/// Panics if given paths are not absolute.
+pub fn relative_url(from_path: &str, to_path: &str, manipulation: OsPathManipulation) -> String {
+    let relative = os_based::relative(from_path, to_path, manipulation).replace('\\', "/");
+    relative.split('/').enumerate().map(|(i, segment)| {
+        let drive = i == 0 && manipulation == OsPathManipulation::Windows && reg_exp_is_match!(r"^[A-Za-z]:$", segment);
+        if drive { segment.to_owned() } else { encode_uri_component(segment) }
+    }).collect::<Vec<_>>().join("/")
+}
+
+/// Splits a URL path into its path and its query and fragment suffix.
+fn split_suffix(path: &str) -> (&str, &str) {
+    path.split_at(path.find(['?', '#']).unwrap_or(path.len()))
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let absolute = path.starts_with('/');
+    let mut segments = Vec::<&str>::new();
+    let mut trailing_slash = false;
+    for segment in path.split('/').skip(if absolute { 1 } else { 0 }) {
+        trailing_slash = segment == "." || segment == "..";
+        match segment {
+            "." => {},
+            ".." => {
+                segments.pop();
+            },
+            _ => segments.push(segment),
+        }
+    }
+    let mut r = if absolute { "/".to_owned() } else { "".to_owned() };
+    r.push_str(&segments.join("/"));
+    if trailing_slash && !segments.is_empty() {
+        r.push('/');
+    }
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relative_urls() {
+        let common = OsPathManipulation::Common;
+        assert_eq!("../my%20docs/a.html", relative_url("/site/pages", "/site/my docs/a.html", common));
+        assert_eq!("b%23c/%3F%25", relative_url("/a", "/a/b#c/?%", common));
+        assert_eq!("", relative_url("/a", "/a", common));
+
+        let windows = OsPathManipulation::Windows;
+        assert_eq!("../../Program%20Files/x%2By", relative_url(r"C:\Users\me", r"C:\Program Files\x+y", windows));
+        assert_eq!("D:/my%20dir", relative_url("C:/a", "D:/my dir", windows));
+    }
+
+    #[test]
+    fn url_paths() {
+        assert_eq!("/page", resolve_url_path("/section/index.html?x=1#top", "../page"));
+        assert_eq!("/section/other.html#frag", resolve_url_path("/section/index.html?x=1", "other.html#frag"));
+        assert_eq!("/section/sub/", resolve_url_path("/section/", "./sub/"));
+        assert_eq!("/section/", resolve_url_path("/section/sub/index.html", ".."));
+        assert_eq!("/", resolve_url_path("/a/b", "../../../.."));
+        assert_eq!("/x/y", resolve_url_path("/a/b?q", "/x/./y"));
+        assert_eq!("/a/b?y=2", resolve_url_path("/a/b?x=1", "?y=2"));
+        assert_eq!("/c", resolve_url_path("/a/b/", "/c"));
+    }
+}
@@ -19,9 +19,9 @@ pub mod timing;
 pub mod futures;
 pub mod number;
 pub mod runtime;
+pub mod file_paths;
 
 pub use ::chrono as temporal;
-pub use ::file_paths as file_paths;
 
 pub(crate) macro incorrect_runtime_panic {
     () => {
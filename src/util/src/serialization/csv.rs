@@ -0,0 +1,290 @@
+/*!
+Working with CSV data as untyped JSON values.
+
+# Example
+
+```
+use rialight_util::serialization::{csv, json::json};
+
+let rows = json!([
+    { "name": "Ana", "age": 30 },
+    { "name": "Bo, Jr.", "age": 4 },
+]);
+assert_eq!("age,name\n30,Ana\n4,\"Bo, Jr.\"\n", csv::array_to_csv(&rows, None).unwrap());
+```
+*/
+
+use super::json::{Map, Value};
+
+/// The `CsvError` enumeration represents an error that occurred
+/// while converting between CSV and untyped JSON values.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CsvError {
+    /// The value to convert is not an array.
+    NotAnArray,
+    /// An element of the array is not an object.
+    NotAnObject {
+        index: usize,
+    },
+    /// A quoted field starting at the given line is never closed.
+    UnterminatedQuote {
+        line: usize,
+    },
+    /// A record has a different number of fields than the header row.
+    FieldCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Reading the input failed.
+    Io {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnArray => write!(f, "Expected an array of objects"),
+            Self::NotAnObject { index } => write!(f, "Element at index {} is not an object", index),
+            Self::UnterminatedQuote { line } => write!(f, "Unterminated quoted field starting at line {}", line),
+            Self::FieldCount { line, expected, found } => write!(f, "Expected {} fields at line {}, found {}", expected, line, found),
+            Self::Io { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// Converts an array of flat objects into a CSV string with a header row.
+///
+/// If `columns` is `None`, the columns are the union of the keys of
+/// all objects, in the order they are first seen. Missing fields and
+/// `null` produce empty cells, strings are written as they are, and
+/// arrays and objects are JSON-encoded into the cell. Fields are quoted
+/// when they contain a comma, a quote or a line break.
+pub fn array_to_csv(value: &Value, columns: Option<&[&str]>) -> Result<String, CsvError> {
+    let Value::Array(elements) = value else {
+        return Err(CsvError::NotAnArray);
+    };
+    let mut rows = Vec::with_capacity(elements.len());
+    for (index, element) in elements.iter().enumerate() {
+        rows.push(element.as_object().ok_or(CsvError::NotAnObject { index })?);
+    }
+    let columns: Vec<String> = match columns {
+        Some(columns) => columns.iter().map(|c| c.to_string()).collect(),
+        None => {
+            let mut columns = Vec::<String>::new();
+            for key in rows.iter().flat_map(|row| row.keys()) {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+            columns
+        },
+    };
+
+    let mut r = String::new();
+    write_record(&mut r, columns.iter().cloned());
+    for row in rows {
+        write_record(&mut r, columns.iter().map(|c| match row.get(c) {
+            None | Some(Value::Null) => "".to_owned(),
+            Some(Value::String(s)) => s.clone(),
+            Some(v) => v.to_string(),
+        }));
+    }
+    Ok(r)
+}
+
+fn write_record(output: &mut String, fields: impl Iterator<Item = String>) {
+    for (i, field) in fields.enumerate() {
+        if i != 0 {
+            output.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            output.push('"');
+            output.push_str(&field.replace('"', "\"\""));
+            output.push('"');
+        } else {
+            output.push_str(&field);
+        }
+    }
+    output.push('\n');
+}
+
+/// Options for [`csv_to_array`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CsvParseOptions {
+    /// The character separating fields. Defaults to `,`.
+    pub delimiter: char,
+    /// The character enclosing quoted fields. Defaults to `"`.
+    pub quote: char,
+    /// Infers booleans and numbers from unquoted fields, and `null`
+    /// from empty unquoted fields. Defaults to `false`, in which case
+    /// every field is a string.
+    pub infer_types: bool,
+}
+
+impl Default for CsvParseOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: '"',
+            infer_types: false,
+        }
+    }
+}
+
+/// Parses CSV with a header row into an array of objects
+/// keyed by the header fields. Blank lines are skipped, and
+/// a quote character inside a quoted field is escaped by doubling it.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::{csv::{self, CsvParseOptions}, json::json};
+/// let opts = CsvParseOptions { infer_types: true, ..Default::default() };
+/// let rows = csv::csv_to_array("name,age\nAna,30\n".as_bytes(), opts).unwrap();
+/// assert_eq!(json!([{ "name": "Ana", "age": 30 }]), rows);
+/// ```
+pub fn csv_to_array<R: std::io::Read>(mut reader: R, opts: CsvParseOptions) -> Result<Value, CsvError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input).map_err(|error| CsvError::Io { message: error.to_string() })?;
+    let mut records = parse_records(&input, &opts)?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Ok(Value::Array(vec![]));
+    };
+    let mut r = Vec::<Value>::new();
+    for (line, record) in records {
+        if record.len() != header.len() {
+            return Err(CsvError::FieldCount { line, expected: header.len(), found: record.len() });
+        }
+        let mut object = Map::new();
+        for ((key, _), (field, quoted)) in header.iter().zip(record) {
+            let value = if !opts.infer_types || quoted {
+                Value::String(field)
+            } else if field.is_empty() {
+                Value::Null
+            } else {
+                super::json::infer_value(&field)
+            };
+            object.insert(key.clone(), value);
+        }
+        r.push(Value::Object(object));
+    }
+    Ok(Value::Array(r))
+}
+
+/// A record, given as its starting line and its fields, each
+/// along with whether it was quoted.
+type Record = (usize, Vec<(String, bool)>);
+
+fn parse_records(input: &str, opts: &CsvParseOptions) -> Result<Vec<Record>, CsvError> {
+    let mut records = Vec::<Record>::new();
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start_line = line;
+        let mut fields = Vec::<(String, bool)>::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            match chars.next() {
+                Some(ch) if ch == opts.quote && field.is_empty() && !quoted => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            None => return Err(CsvError::UnterminatedQuote { line: start_line }),
+                            Some(ch) if ch == opts.quote => {
+                                if chars.peek() != Some(&opts.quote) {
+                                    break;
+                                }
+                                chars.next();
+                                field.push(ch);
+                            },
+                            Some(ch) => {
+                                if ch == '\n' {
+                                    line += 1;
+                                }
+                                field.push(ch);
+                            },
+                        }
+                    }
+                },
+                Some(ch) if ch == opts.delimiter => {
+                    fields.push((std::mem::take(&mut field), quoted));
+                    quoted = false;
+                },
+                Some('\r') if chars.peek() == Some(&'\n') => {},
+                Some('\n') | None => {
+                    line += 1;
+                    fields.push((std::mem::take(&mut field), quoted));
+                    break;
+                },
+                Some(ch) => field.push(ch),
+            }
+        }
+        let blank = fields.len() == 1 && fields[0].0.is_empty() && !fields[0].1;
+        if !blank {
+            records.push((start_line, fields));
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json::json;
+
+    #[test]
+    fn consistent_rows() {
+        let rows = json!([
+            { "a": 1, "b": "x\"y" },
+            { "a": true, "b": [1, 2] },
+        ]);
+        assert_eq!("a,b\n1,\"x\"\"y\"\ntrue,\"[1,2]\"\n", array_to_csv(&rows, None).unwrap());
+        assert_eq!("b,a\n\"x\"\"y\",1\n\"[1,2]\",true\n", array_to_csv(&rows, Some(&["b", "a"])).unwrap());
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let rows = json!([
+            { "a": 1 },
+            { "b": 2, "a": null },
+            {},
+        ]);
+        assert_eq!("a,b\n1,\n,2\n,\n", array_to_csv(&rows, None).unwrap());
+        assert_eq!(Err(CsvError::NotAnObject { index: 1 }), array_to_csv(&json!([{}, 1]), None));
+        assert_eq!(Err(CsvError::NotAnArray), array_to_csv(&json!({}), None));
+    }
+
+    #[test]
+    fn parse_typical() {
+        let input = "a,b\r\n1,x\r\n\r\n2,y\r\n";
+        assert_eq!(json!([{ "a": "1", "b": "x" }, { "a": "2", "b": "y" }]), csv_to_array(input.as_bytes(), CsvParseOptions::default()).unwrap());
+        let opts = CsvParseOptions { delimiter: ';', quote: '\'', infer_types: false };
+        assert_eq!(json!([{ "a": "b;c" }]), csv_to_array("a\n'b;c'".as_bytes(), opts).unwrap());
+        assert_eq!(json!([]), csv_to_array("".as_bytes(), CsvParseOptions::default()).unwrap());
+        assert_eq!(Err(CsvError::FieldCount { line: 3, expected: 2, found: 1 }), csv_to_array("a,b\n1,2\n3".as_bytes(), CsvParseOptions::default()));
+    }
+
+    #[test]
+    fn parse_quoted() {
+        let input = "name,note\n\"Bo, Jr.\",\"said \"\"hi\"\"\nthen left\"\n";
+        assert_eq!(json!([{ "name": "Bo, Jr.", "note": "said \"hi\"\nthen left" }]), csv_to_array(input.as_bytes(), CsvParseOptions::default()).unwrap());
+        assert_eq!(Err(CsvError::UnterminatedQuote { line: 2 }), csv_to_array("a\n\"x".as_bytes(), CsvParseOptions::default()));
+    }
+
+    #[test]
+    fn parse_inferred() {
+        let opts = CsvParseOptions { infer_types: true, ..Default::default() };
+        let input = "a,b,c,d\n1,2.5,true,\n-3,\"4\",x,\"\"\n";
+        assert_eq!(json!([
+            { "a": 1, "b": 2.5, "c": true, "d": null },
+            { "a": -3, "b": "4", "c": "x", "d": "" },
+        ]), csv_to_array(input.as_bytes(), opts).unwrap());
+        let rows = json!([{ "a": 1, "b": "x,y" }, { "a": null, "b": false }]);
+        assert_eq!(rows, csv_to_array(array_to_csv(&rows, None).unwrap().as_bytes(), opts).unwrap());
+    }
+}
@@ -30,6 +30,159 @@ pub use serde_json::{
 pub use serde_json::json;
 pub use self::error::Result;
 
+mod pointer;
+
+mod diff;
+pub use self::diff::{diff, make_patch, pretty_diff, Difference};
+
+mod progress;
+pub use self::progress::{deserialize_from_reader_progress, deserialize_from_reader_progress_sized};
+
+mod key_path;
+pub use self::key_path::{
+    apply_overrides,
+    dotted_to_pointer,
+    from_pairs,
+    from_pairs_with_policy,
+    pointer_to_dotted,
+    ConflictPolicy,
+    KeyConflictError,
+    OverrideError,
+};
+pub(crate) use self::key_path::infer_value;
+
+mod round_trip;
+pub use self::round_trip::{round_trips_identically, RoundTripOptions};
+
+mod access;
+pub use self::access::{
+    expect_array_mut,
+    expect_object_mut,
+    project,
+    sorted_entries,
+    to_vec_lenient,
+    try_index,
+    Index,
+    IndexError,
+    ValueError,
+};
+
+pub mod query_string;
+
+mod non_finite;
+pub use self::non_finite::{serialize_with_nonfinite, NonFinitePolicy};
+
+mod traversal;
+pub use self::traversal::{
+    merge,
+    merge_tracked,
+    string_leaves,
+    string_leaves_with_keys,
+    walk,
+    DepthLimitError,
+};
+
+mod lenient;
+pub use self::lenient::{
+    deserialize_any_encoding,
+    deserialize_from_slice_tolerant,
+    deserialize_lenient_commas,
+    deserialize_tolerant,
+    CodecError,
+};
+
+mod transform;
+pub use self::transform::{
+    canonicalize_numbers,
+    filter_array,
+    map_array,
+    rename_keys,
+    rename_keys_with_policy,
+    transform_keys,
+    KeyStyle,
+    NumberPolicy,
+};
+
+mod flatten;
+pub use self::flatten::{
+    to_env_vars,
+    to_string_map,
+    to_string_map_bounded,
+    to_table,
+    FlattenError,
+    TableError,
+};
+
+mod lines;
+pub use self::lines::{from_lines, to_lines, LinesError};
+
+mod formatting;
+pub use self::formatting::{
+    serialize_ascii,
+    serialize_bounded,
+    serialize_header_safe,
+    serialize_pages,
+    serialize_pretty_ansi,
+    serialize_pretty_styled,
+    PageError,
+    StyleKind,
+};
+
+pub mod jsonc_dom;
+
+pub mod lexer;
+
+mod ndjson;
+pub use self::ndjson::{filter_ndjson, filter_ndjson_with_skips, transform_ndjson};
+
+#[cfg(feature = "std-fs")]
+mod files;
+#[cfg(feature = "std-fs")]
+pub use self::files::{merge_json_files, LoadError};
+
+mod limits;
+pub use self::limits::{deserialize_with_limits, LimitError, Limits};
+
+mod strict;
+pub use self::strict::{deserialize_reject_duplicates, DuplicateKeyError};
+
+mod refs;
+pub use self::refs::{resolve_refs, resolve_refs_bounded, RefError};
+
+mod template;
+pub use self::template::{render_template, TemplateError};
+
+mod repair;
+pub use self::repair::{repair, repair_reporting, Repair, RepairError};
+
+mod schema;
+pub use self::schema::infer_schema;
+
+mod size;
+pub use self::size::estimated_heap_size;
+
+/// Reads a nested value through a chain of object keys and array indices,
+/// returning `Option<&Value>` instead of panicking or yielding `Value::Null`
+/// on a missing key, like chained indexing does.
+///
+/// The first argument is a `Value` or a reference to one.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{get, json};
+///
+/// let v = json!({"a": {"b": [10, 20]}});
+/// assert_eq!(Some(&json!(20)), get!(v, "a", "b", 1));
+/// assert_eq!(None, get!(v, "a", "c", 0));
+/// ```
+pub macro get {
+    ($value:expr, $($key:expr),+ $(,)?) => {
+        ::std::option::Option::Some(&$value)
+            $(.and_then(|v| v.get($key)))+
+    }
+}
+
 /// Deserializes a JSON string into a value.
 pub fn deserialize<'a, T>(string: &'a str) -> Result<T>
     where T: Deserialize<'a>
@@ -221,4 +374,22 @@ pub mod map {
         ValuesMut,
         Entry,
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_macro() {
+        let v = json!({"a": {"b": [10, {"c": true}]}, "d": null});
+        assert_eq!(Some(&json!(10)), get!(v, "a", "b", 0));
+        assert_eq!(Some(&json!(true)), get!(&v, "a", "b", 1, "c"));
+        assert_eq!(Some(&Value::Null), get!(v, "d"));
+        assert_eq!(None, get!(v, "a", "x"));
+        assert_eq!(None, get!(v, "a", "b", 2));
+        assert_eq!(None, get!(v, "a", "b", "c"));
+        let key = String::from("a");
+        assert!(get!(v, &key, "b").unwrap().is_array());
+    }
+}
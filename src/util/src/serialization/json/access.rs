@@ -0,0 +1,325 @@
+use serde::de::Error as _;
+use super::{pointer, Error, Map, Value};
+use super::super::generic_deserialization::DeserializeOwned;
+
+/// The `ValueError` structure represents an error that occurred
+/// because a value does not have the expected kind.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValueError {
+    /// The context given by the caller, such as the location of the value.
+    pub context: String,
+    /// The expected kind, such as `object`.
+    pub expected: &'static str,
+    /// The actual kind of the value.
+    pub actual: &'static str,
+}
+
+impl std::fmt::Display for ValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected {} to be an {}, found {}", self.context, self.expected, kind_name(self.actual))
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn kind_name(kind: &str) -> String {
+    match kind {
+        "null" => kind.to_owned(),
+        "array" | "object" => format!("an {}", kind),
+        _ => format!("a {}", kind),
+    }
+}
+
+/// Returns the object map of a value, or a [`ValueError`] mentioning
+/// `context` and the actual kind if the value is not an object.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut value = json!({"server": {}});
+/// json::expect_object_mut(&mut value["server"], "server").unwrap().insert("port".into(), json!(80));
+/// assert_eq!(json!({"server": {"port": 80}}), value);
+/// ```
+pub fn expect_object_mut<'a>(value: &'a mut Value, context: &str) -> Result<&'a mut Map<String, Value>, ValueError> {
+    let actual = kind_of(value);
+    value.as_object_mut().ok_or_else(|| ValueError { context: context.to_owned(), expected: "object", actual })
+}
+
+/// Returns the elements of a value, or a [`ValueError`] mentioning
+/// `context` and the actual kind if the value is not an array.
+pub fn expect_array_mut<'a>(value: &'a mut Value, context: &str) -> Result<&'a mut Vec<Value>, ValueError> {
+    let actual = kind_of(value);
+    value.as_array_mut().ok_or_else(|| ValueError { context: context.to_owned(), expected: "array", actual })
+}
+
+/// An object key or array index for [`try_index`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Index<'a> {
+    /// A key of an object.
+    Key(&'a str),
+    /// An index of an array.
+    Position(usize),
+}
+
+impl<'a> From<&'a str> for Index<'a> {
+    fn from(key: &'a str) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<usize> for Index<'_> {
+    fn from(index: usize) -> Self {
+        Self::Position(index)
+    }
+}
+
+/// The `IndexError` enumeration represents an error that occurred
+/// while indexing a value with [`try_index`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum IndexError {
+    /// The object has no such key.
+    KeyAbsent {
+        key: String,
+    },
+    /// The index is not less than the length of the array.
+    OutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    /// The value is not the kind of container that the index requires.
+    WrongType {
+        expected: &'static str,
+        actual: &'static str,
+    },
+}
+
+impl std::fmt::Display for IndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KeyAbsent { key } => write!(f, "Key {:?} is absent", key),
+            Self::OutOfBounds { index, len } => write!(f, "Index {} is out of bounds for an array of length {}", index, len),
+            Self::WrongType { expected, actual } => write!(f, "Expected an {}, found {}", expected, kind_name(actual)),
+        }
+    }
+}
+
+impl std::error::Error for IndexError {}
+
+/// Indexes a value by an object key or array index like `value[key]`
+/// does, but returns an [`IndexError`] describing why no element is
+/// found, whereas indexing yields `null`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json, Index, IndexError};
+/// let value = json!({"a": [1, 2]});
+/// assert_eq!(Ok(&json!(2)), json::try_index(&value["a"], Index::Position(1)));
+/// assert_eq!(Err(IndexError::KeyAbsent { key: "b".into() }), json::try_index(&value, "b".into()));
+/// ```
+pub fn try_index<'a>(value: &'a Value, key: Index) -> Result<&'a Value, IndexError> {
+    match (key, value) {
+        (Index::Key(key), Value::Object(fields)) => fields.get(key).ok_or_else(|| IndexError::KeyAbsent { key: key.to_owned() }),
+        (Index::Position(index), Value::Array(elements)) => elements.get(index).ok_or(IndexError::OutOfBounds { index, len: elements.len() }),
+        (Index::Key(_), _) => Err(IndexError::WrongType { expected: "object", actual: kind_of(value) }),
+        (Index::Position(_), _) => Err(IndexError::WrongType { expected: "array", actual: kind_of(value) }),
+    }
+}
+
+/// Interprets each element of an array as an instance of type `T`,
+/// returning the elements that succeed along with the index and error
+/// of each element that fails. A value that is not an array gives a
+/// single error at index 0.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let (numbers, errors) = json::to_vec_lenient::<u8>(&json!([1, "x", 3]));
+/// assert_eq!(vec![1, 3], numbers);
+/// assert_eq!(1, errors[0].0);
+/// ```
+pub fn to_vec_lenient<T: DeserializeOwned>(value: &Value) -> (Vec<T>, Vec<(usize, Error)>) {
+    let Some(elements) = value.as_array() else {
+        return (vec![], vec![(0, Error::custom("expected an array"))]);
+    };
+    let mut r = Vec::<T>::new();
+    let mut errors = Vec::<(usize, Error)>::new();
+    for (i, element) in elements.iter().enumerate() {
+        match T::deserialize(element) {
+            Ok(item) => r.push(item),
+            Err(error) => errors.push((i, error)),
+        }
+    }
+    (r, errors)
+}
+
+/// Returns the entries of an object sorted by key, or `None`
+/// if the value is not an object.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let value = json!({"b": 2, "a": 1});
+/// let keys: Vec<&String> = json::sorted_entries(&value).unwrap().into_iter().map(|(k, _)| k).collect();
+/// assert_eq!(vec!["a", "b"], keys);
+/// ```
+pub fn sorted_entries(value: &Value) -> Option<Vec<(&String, &Value)>> {
+    let mut entries: Vec<(&String, &Value)> = value.as_object()?.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    Some(entries)
+}
+
+/// Builds a value containing only the nodes at the given JSON Pointers,
+/// reconstructing the objects and arrays that enclose them.
+///
+/// Pointers that do not resolve to a node are skipped. Selected array
+/// elements keep their relative order, but unselected elements are
+/// omitted, therefore indices may shift.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let value = json!({"a": {"b": 1, "x": 2}, "c": [3, 4], "d": 5});
+/// assert_eq!(json!({"a": {"b": 1}, "c": [3]}), json::project(&value, &["/a/b", "/c/0"]));
+/// ```
+pub fn project(value: &Value, pointers: &[&str]) -> Value {
+    let selections: Vec<Vec<String>> = pointers.iter()
+        .filter(|p| value.pointer(p).is_some())
+        .filter_map(|p| pointer::parse(p))
+        .collect();
+    if selections.is_empty() {
+        return match value {
+            Value::Array(_) => Value::Array(vec![]),
+            _ => Value::Object(Map::new()),
+        };
+    }
+    project_at(value, &selections.iter().map(|s| s.as_slice()).collect::<Vec<_>>())
+}
+
+fn project_at(value: &Value, selections: &[&[String]]) -> Value {
+    if selections.iter().any(|s| s.is_empty()) {
+        return value.clone();
+    }
+    let children_of = |token: &str| -> Vec<&[String]> {
+        selections.iter().filter(|s| s[0] == token).map(|s| &s[1..]).collect()
+    };
+    match value {
+        Value::Object(map) => {
+            let mut r = Map::new();
+            for (k, v) in map {
+                let children = children_of(k);
+                if !children.is_empty() {
+                    r.insert(k.clone(), project_at(v, &children));
+                }
+            }
+            Value::Object(r)
+        },
+        Value::Array(elements) => {
+            Value::Array(elements.iter().enumerate().filter_map(|(i, v)| {
+                let children = children_of(&i.to_string());
+                if children.is_empty() { None } else { Some(project_at(v, &children)) }
+            }).collect())
+        },
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn indexing() {
+        let value = json!({"a": [1, {"b": null}], "s": "x"});
+        assert_eq!(Ok(&json!([1, {"b": null}])), try_index(&value, Index::Key("a")));
+        assert_eq!(Ok(&json!(null)), try_index(&value["a"][1], "b".into()));
+        assert_eq!(Ok(&json!(1)), try_index(&value["a"], 0.into()));
+
+        assert_eq!(Err(IndexError::KeyAbsent { key: "z".into() }), try_index(&value, Index::Key("z")));
+        assert_eq!(Err(IndexError::OutOfBounds { index: 2, len: 2 }), try_index(&value["a"], Index::Position(2)));
+        assert_eq!(Err(IndexError::WrongType { expected: "array", actual: "object" }), try_index(&value, Index::Position(0)));
+        assert_eq!(Err(IndexError::WrongType { expected: "object", actual: "array" }), try_index(&value["a"], Index::Key("0")));
+        assert_eq!(Err(IndexError::WrongType { expected: "object", actual: "null" }), try_index(&Value::Null, Index::Key("a")));
+
+        assert_eq!("Key \"z\" is absent", try_index(&value, Index::Key("z")).unwrap_err().to_string());
+        assert_eq!("Index 2 is out of bounds for an array of length 2", try_index(&value["a"], Index::Position(2)).unwrap_err().to_string());
+        assert_eq!("Expected an array, found a string", try_index(&value["s"], Index::Position(0)).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn expect_mut() {
+        let mut value = json!({"list": [1], "name": "x", "none": null});
+        expect_array_mut(&mut value["list"], "list").unwrap().push(json!(2));
+        expect_object_mut(&mut value, "root").unwrap().remove("none");
+        assert_eq!(json!({"list": [1, 2], "name": "x"}), value);
+
+        let error = expect_object_mut(&mut value["name"], "the name").unwrap_err();
+        assert_eq!(ValueError { context: "the name".into(), expected: "object", actual: "string" }, error);
+        assert_eq!("Expected the name to be an object, found a string", error.to_string());
+        let error = expect_array_mut(&mut value["missing"], "/missing").unwrap_err();
+        assert_eq!("Expected /missing to be an array, found null", error.to_string());
+        assert_eq!("Expected x to be an array, found an object", expect_array_mut(&mut json!({}), "x").unwrap_err().to_string());
+    }
+
+    #[test]
+    fn lenient_vec() {
+        #[derive(crate::serialization::Deserialize, PartialEq, Debug)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        let value = json!([{"x": 1, "y": 2}, {"x": 3}, {"x": 5, "y": 6}, 7]);
+        let (points, errors) = to_vec_lenient::<Point>(&value);
+        assert_eq!(vec![Point { x: 1, y: 2 }, Point { x: 5, y: 6 }], points);
+        assert_eq!(vec![1, 3], errors.iter().map(|(i, _)| *i).collect::<Vec<_>>());
+        assert!(errors[0].1.to_string().contains("missing field `y`"));
+
+        let (strings, errors) = to_vec_lenient::<String>(&json!(["a", "b"]));
+        assert_eq!(vec!["a", "b"], strings);
+        assert!(errors.is_empty());
+
+        let (items, errors) = to_vec_lenient::<String>(&json!({"a": 1}));
+        assert!(items.is_empty());
+        assert_eq!(0, errors[0].0);
+    }
+
+    #[test]
+    fn sorted() {
+        let value = json!({"zeta": 1, "alpha": 2, "Mu": 3, "beta": 4});
+        let entries = sorted_entries(&value).unwrap();
+        assert_eq!(vec!["Mu", "alpha", "beta", "zeta"], entries.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>());
+        assert_eq!(&json!(2), entries[1].1);
+        assert_eq!(None, sorted_entries(&json!([1, 2])));
+    }
+
+    #[test]
+    fn projection() {
+        let value = json!({
+            "a": {"b": {"deep": true}, "x": 1},
+            "c": [{"n": 0}, {"n": 1}, {"n": 2}],
+            "d/e": 2,
+            "f": "unused"
+        });
+        assert_eq!(json!({"a": {"b": {"deep": true}}, "c": [{"n": 0}]}), project(&value, &["/a/b", "/c/0"]));
+        assert_eq!(json!({"c": [{"n": 0}, {"n": 2}], "d/e": 2}), project(&value, &["/c/2", "/c/0", "/d~1e", "/missing", "/c/9"]));
+        assert_eq!(json!({"a": {"x": 1}}), project(&value, &["/a/x", "/a/x"]));
+        assert_eq!(json!({}), project(&value, &["/missing"]));
+        assert_eq!(value, project(&value, &[""]));
+    }
+}
@@ -0,0 +1,268 @@
+use super::{pointer, Map, Value};
+
+/// A difference found between two JSON values by [`diff`].
+/// Each variant carries the JSON Pointer at which the difference occurs.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Difference {
+    /// A value that is present only in the second value.
+    Added {
+        pointer: String,
+        value: Value,
+    },
+    /// A value that is present only in the first value.
+    Removed {
+        pointer: String,
+        value: Value,
+    },
+    /// A value that differs between both values.
+    Changed {
+        pointer: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl Difference {
+    /// The JSON Pointer at which the difference occurs.
+    pub fn pointer(&self) -> &str {
+        match self {
+            Self::Added { pointer, .. } |
+            Self::Removed { pointer, .. } |
+            Self::Changed { pointer, .. } => pointer,
+        }
+    }
+}
+
+/// Structurally compares two JSON values, returning the
+/// differences from `a` to `b` in document order.
+///
+/// Objects are compared key by key and arrays are compared
+/// index by index. Values of different types are reported
+/// as a single change.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let differences = json::diff(&json!({"a": 1, "b": 2}), &json!({"a": 1, "b": 3}));
+/// assert_eq!(1, differences.len());
+/// assert_eq!("/b", differences[0].pointer());
+/// ```
+pub fn diff(a: &Value, b: &Value) -> Vec<Difference> {
+    let mut r = Vec::<Difference>::new();
+    diff_at("", a, b, &mut r);
+    r
+}
+
+fn diff_at(at: &str, a: &Value, b: &Value, r: &mut Vec<Difference>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, a_v) in a {
+                let p = pointer::push_token(at, k);
+                match b.get(k) {
+                    Some(b_v) => diff_at(&p, a_v, b_v, r),
+                    None => r.push(Difference::Removed { pointer: p, value: a_v.clone() }),
+                }
+            }
+            for (k, b_v) in b {
+                if !a.contains_key(k) {
+                    r.push(Difference::Added { pointer: pointer::push_token(at, k), value: b_v.clone() });
+                }
+            }
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..usize::max(a.len(), b.len()) {
+                let p = format!("{}/{}", at, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(a_v), Some(b_v)) => diff_at(&p, a_v, b_v, r),
+                    (Some(a_v), None) => r.push(Difference::Removed { pointer: p, value: a_v.clone() }),
+                    (None, Some(b_v)) => r.push(Difference::Added { pointer: p, value: b_v.clone() }),
+                    (None, None) => {},
+                }
+            }
+        },
+        (a, b) => {
+            if a != b {
+                r.push(Difference::Changed { pointer: at.to_owned(), from: a.clone(), to: b.clone() });
+            }
+        },
+    }
+}
+
+/// Generates a JSON Patch ([RFC 6902](https://www.rfc-editor.org/rfc/rfc6902))
+/// transforming `a` into `b`, as a `Value::Array` of `add`, `remove` and
+/// `replace` operations.
+///
+/// Objects are compared key by key and arrays index by index, as in
+/// [`diff`]: differing elements are replaced, extra elements of `a` are
+/// removed from the last one and missing elements are added in order.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let patch = json::make_patch(&json!({"a": 1, "b": 2}), &json!({"a": 1, "c": 3}));
+/// assert_eq!(json!([
+///     {"op": "remove", "path": "/b"},
+///     {"op": "add", "path": "/c", "value": 3},
+/// ]), patch);
+/// ```
+pub fn make_patch(a: &Value, b: &Value) -> Value {
+    let mut r = Vec::<Value>::new();
+    make_patch_at("", a, b, &mut r);
+    Value::Array(r)
+}
+
+fn make_patch_at(at: &str, a: &Value, b: &Value, r: &mut Vec<Value>) {
+    let operation = |op: &str, path: String, value: Option<&Value>| {
+        let mut o = Map::new();
+        o.insert("op".into(), Value::String(op.into()));
+        o.insert("path".into(), Value::String(path));
+        if let Some(value) = value {
+            o.insert("value".into(), value.clone());
+        }
+        Value::Object(o)
+    };
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, a_v) in a {
+                let p = pointer::push_token(at, k);
+                match b.get(k) {
+                    Some(b_v) => make_patch_at(&p, a_v, b_v, r),
+                    None => r.push(operation("remove", p, None)),
+                }
+            }
+            for (k, b_v) in b {
+                if !a.contains_key(k) {
+                    r.push(operation("add", pointer::push_token(at, k), Some(b_v)));
+                }
+            }
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            for (i, (a_v, b_v)) in a.iter().zip(b.iter()).enumerate() {
+                make_patch_at(&format!("{}/{}", at, i), a_v, b_v, r);
+            }
+            for i in (b.len()..a.len()).rev() {
+                r.push(operation("remove", format!("{}/{}", at, i), None));
+            }
+            for (i, b_v) in b.iter().enumerate().skip(a.len()) {
+                r.push(operation("add", format!("{}/{}", at, i), Some(b_v)));
+            }
+        },
+        (a, b) => {
+            if a != b {
+                r.push(operation("replace", at.to_owned(), Some(b)));
+            }
+        },
+    }
+}
+
+/// Renders the differences between an expected and an actual
+/// JSON value as an unified-diff-style string, intended for
+/// test output.
+///
+/// Each difference is introduced by a `@@ pointer @@` line,
+/// followed by a `-` line with the expected value and/or a
+/// `+` line with the actual value. If both values are equal,
+/// an empty string is returned.
+pub fn pretty_diff(expected: &Value, actual: &Value) -> String {
+    let differences = diff(expected, actual);
+    if differences.is_empty() {
+        return "".to_owned();
+    }
+    let mut r = "--- expected\n+++ actual\n".to_owned();
+    for d in differences {
+        r.push_str(&format!("@@ {} @@\n", d.pointer()));
+        match d {
+            Difference::Added { value, .. } => {
+                r.push_str(&format!("+ {}\n", value));
+            },
+            Difference::Removed { value, .. } => {
+                r.push_str(&format!("- {}\n", value));
+            },
+            Difference::Changed { from, to, .. } => {
+                r.push_str(&format!("- {}\n+ {}\n", from, to));
+            },
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn structural_diff() {
+        let a = json!({"a": {"b": 1}, "c": [1, 2], "d/e": true});
+        let b = json!({"a": {"b": 2}, "c": [1], "d/e": true, "f": null});
+        assert_eq!(vec![
+            Difference::Changed { pointer: "/a/b".into(), from: json!(1), to: json!(2) },
+            Difference::Removed { pointer: "/c/1".into(), value: json!(2) },
+            Difference::Added { pointer: "/f".into(), value: json!(null) },
+        ], diff(&a, &b));
+        assert!(diff(&a, &a).is_empty());
+    }
+
+    fn apply_patch(value: &mut Value, patch: &Value) {
+        for operation in patch.as_array().unwrap() {
+            let op = operation["op"].as_str().unwrap();
+            let mut tokens = pointer::parse(operation["path"].as_str().unwrap()).unwrap();
+            let Some(last) = tokens.pop() else {
+                *value = operation["value"].clone();
+                continue;
+            };
+            let mut target = &mut *value;
+            for token in tokens {
+                target = match target {
+                    Value::Array(a) => &mut a[token.parse::<usize>().unwrap()],
+                    _ => &mut target[token.as_str()],
+                };
+            }
+            match (op, target) {
+                ("remove", Value::Array(a)) => { a.remove(last.parse().unwrap()); },
+                ("remove", Value::Object(o)) => { o.remove(&last); },
+                ("add", Value::Array(a)) => a.insert(last.parse().unwrap(), operation["value"].clone()),
+                ("replace", Value::Array(a)) => a[last.parse::<usize>().unwrap()] = operation["value"].clone(),
+                (_, Value::Object(o)) => { o.insert(last, operation["value"].clone()); },
+                _ => panic!("invalid patch operation"),
+            }
+        }
+    }
+
+    #[test]
+    fn patch_generation() {
+        let pairs = [
+            (json!({"a": {"b": 1}, "c": [1, 2, 3], "d/e": true}), json!({"a": {"b": 2}, "c": [1], "f": null})),
+            (json!([1, [2, 3]]), json!([0, [2, 3, 4], {"x": "y"}])),
+            (json!({"a": [1, 2]}), json!({"a": {"0": 1}})),
+            (json!({"~k": 1}), json!({"~k": 2, "a/b": 3})),
+            (json!(1), json!("x")),
+            (json!([]), json!([])),
+        ];
+        for (a, b) in pairs {
+            let patch = make_patch(&a, &b);
+            let mut patched = a.clone();
+            apply_patch(&mut patched, &patch);
+            assert_eq!(b, patched, "patch: {}", patch);
+        }
+
+        assert_eq!(json!([
+            {"op": "replace", "path": "/0", "value": 9},
+            {"op": "remove", "path": "/3"},
+            {"op": "remove", "path": "/2"},
+        ]), make_patch(&json!([1, 2, 3, 4]), &json!([9, 2])));
+        assert_eq!(json!([{"op": "replace", "path": "", "value": null}]), make_patch(&json!(1), &json!(null)));
+        assert_eq!(json!([]), make_patch(&json!({"a": [1]}), &json!({"a": [1]})));
+    }
+
+    #[test]
+    fn pretty() {
+        let r = pretty_diff(&json!({"a": {"b": "x"}}), &json!({"a": {"b": "y"}}));
+        assert!(r.contains("@@ /a/b @@"));
+        assert!(r.contains("- \"x\""));
+        assert!(r.contains("+ \"y\""));
+        assert_eq!("", pretty_diff(&json!([1]), &json!([1])));
+    }
+}
@@ -0,0 +1,48 @@
+use super::{merge, Map, Value};
+use crate::file_paths::os_based::{self, OsPathManipulation};
+
+/// The `LoadError` enumeration represents an error that occurred
+/// while loading a JSON file. Each variant names the resolved path
+/// of the offending file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io {
+        path: String,
+        message: String,
+    },
+    /// The file does not contain valid JSON.
+    Json {
+        path: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "Could not read {:?}: {}", path, message),
+            Self::Json { path, message } => write!(f, "Invalid JSON in {:?}: {}", path, message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Reads JSON files and deep-merges them in order, as [`merge`] does,
+/// so that later files override earlier ones. Relative paths are resolved
+/// against the current working directory. An empty list of paths gives
+/// an empty object.
+///
+/// This function is only available with the `std-fs` feature.
+pub fn merge_json_files(paths: &[&str], manipulation: OsPathManipulation) -> Result<Value, LoadError> {
+    let mut r = Value::Object(Map::new());
+    for path in paths {
+        let path = os_based::to_absolute(path, manipulation).map_err(|e| LoadError::Io { path: path.to_string(), message: e.to_string() })?;
+        let text = std::fs::read_to_string(&path).map_err(|e| LoadError::Io { path: path.clone(), message: e.to_string() })?;
+        let layer: Value = serde_json::from_str(&text).map_err(|e| LoadError::Json { path: path.clone(), message: e.to_string() })?;
+        // parsing already limits the nesting depth to 128
+        merge(&mut r, &layer, usize::MAX).unwrap();
+    }
+    Ok(r)
+}
@@ -0,0 +1,278 @@
+use super::Value;
+use std::collections::HashMap;
+
+/// The `FlattenError` enumeration represents an error that occurred
+/// while flattening a value into a map.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FlattenError {
+    /// The value is nested deeper than the maximum depth.
+    TooDeep {
+        max_depth: usize,
+    },
+    /// Two different nodes flatten to the same key,
+    /// such as `{"a.b": 1, "a": {"b": 2}}`.
+    KeyCollision {
+        key: String,
+    },
+}
+
+impl std::fmt::Display for FlattenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooDeep { max_depth } => write!(f, "Value exceeds the maximum nesting depth of {}", max_depth),
+            Self::KeyCollision { key } => write!(f, "Multiple values flatten to the key {:?}", key),
+        }
+    }
+}
+
+impl std::error::Error for FlattenError {}
+
+/// The `TableError` enumeration represents an error that occurred
+/// while expanding a value into a table.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TableError {
+    /// The value is not an array.
+    NotAnArray,
+    /// An element of the array is not an object.
+    NotAnObject {
+        index: usize,
+    },
+}
+
+impl std::fmt::Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnArray => write!(f, "Expected an array of objects"),
+            Self::NotAnObject { index } => write!(f, "Element {} is not an object", index),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// Expands an array of objects into table columns and rows. Nested
+/// objects become columns whose names join the keys with `separator`,
+/// such as `address.city`, while other values, including arrays and
+/// empty objects, are cells.
+///
+/// The columns are the union of the columns of all objects, in the
+/// order they are first seen. Missing cells are `null`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let (columns, rows) = json::to_table(&json!([{"a": {"b": 1}}, {"c": 2}]), ".").unwrap();
+/// assert_eq!(vec!["a.b", "c"], columns);
+/// assert_eq!(vec![vec![json!(1), json!(null)], vec![json!(null), json!(2)]], rows);
+/// ```
+pub fn to_table(value: &Value, separator: &str) -> Result<(Vec<String>, Vec<Vec<Value>>), TableError> {
+    let elements = value.as_array().ok_or(TableError::NotAnArray)?;
+    let mut columns = Vec::<String>::new();
+    let mut indices = HashMap::<String, usize>::new();
+    let mut rows = Vec::<Vec<(usize, Value)>>::new();
+    for (index, element) in elements.iter().enumerate() {
+        if !element.is_object() {
+            return Err(TableError::NotAnObject { index });
+        }
+        let mut cells = Vec::<(String, &Value)>::new();
+        append_cells(&mut cells, element, None, separator);
+        rows.push(cells.into_iter().map(|(column, v)| {
+            let i = *indices.entry(column.clone()).or_insert_with(|| {
+                columns.push(column);
+                columns.len() - 1
+            });
+            (i, v.clone())
+        }).collect());
+    }
+    let rows = rows.into_iter().map(|cells| {
+        let mut row = vec![Value::Null; columns.len()];
+        for (i, v) in cells {
+            row[i] = v;
+        }
+        row
+    }).collect();
+    Ok((columns, rows))
+}
+
+fn append_cells<'a>(output: &mut Vec<(String, &'a Value)>, value: &'a Value, column: Option<String>, separator: &str) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() || column.is_none() => {
+            for (key, field) in fields {
+                let column = match &column {
+                    Some(column) => column.clone() + separator + key,
+                    None => key.clone(),
+                };
+                append_cells(output, field, Some(column), separator);
+            }
+        },
+        _ => output.push((column.unwrap_or_default(), value)),
+    }
+}
+
+/// Flattens an object into environment variable pairs, such as
+/// `{"db": {"host": "x"}}` into `DB_HOST=x`.
+///
+/// Keys are joined with `_` and uppercased, and any character other than
+/// an ASCII letter, digit or `_` is replaced by `_`. A non-empty `prefix`
+/// is prepended with a `_` separator. Strings are used as they are, `null`
+/// becomes an empty string, and other values, including arrays, are
+/// JSON-encoded. Empty objects produce no pairs.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let vars = json::to_env_vars(&json!({"db": {"host": "x", "port": 5432}}), "app");
+/// assert_eq!(vec![("APP_DB_HOST".to_owned(), "x".to_owned()), ("APP_DB_PORT".to_owned(), "5432".to_owned())], vars);
+/// ```
+pub fn to_env_vars(value: &Value, prefix: &str) -> Vec<(String, String)> {
+    let mut r = Vec::<(String, String)>::new();
+    let prefix = env_var_name(prefix.trim_end_matches('_'));
+    append_env_vars(&mut r, value, prefix);
+    r
+}
+
+fn append_env_vars(output: &mut Vec<(String, String)>, value: &Value, name: String) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let key = env_var_name(key);
+                let name = if name.is_empty() { key } else { name.clone() + "_" + &key };
+                append_env_vars(output, field, name);
+            }
+        },
+        Value::String(s) => output.push((name, s.clone())),
+        Value::Null => output.push((name, "".to_owned())),
+        _ => output.push((name, value.to_string())),
+    }
+}
+
+fn env_var_name(key: &str) -> String {
+    key.chars().map(|ch| if ch.is_ascii_alphanumeric() { ch.to_ascii_uppercase() } else { '_' }).collect()
+}
+
+/// Flattens a value into a map from dotted keys to stringified leaves,
+/// such as `{"a": {"b": [true]}}` into `a.b.0 = true`.
+///
+/// Object keys and array indices are joined with `.`. Strings are used
+/// as they are and other leaves, including empty objects and arrays, are
+/// JSON-encoded. A scalar root is stored under the empty key. Fails if
+/// two nodes flatten to the same key.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let map = json::to_string_map(&json!({"db": {"host": "x", "port": 5432}})).unwrap();
+/// assert_eq!("x", map["db.host"]);
+/// assert_eq!("5432", map["db.port"]);
+/// ```
+pub fn to_string_map(value: &Value) -> Result<HashMap<String, String>, FlattenError> {
+    flatten_to_map(value, None)
+}
+
+/// Flattens a value into a map as [`to_string_map`] does, failing if
+/// the value is nested deeper than `max_depth`. The root is at depth 0.
+pub fn to_string_map_bounded(value: &Value, max_depth: usize) -> Result<HashMap<String, String>, FlattenError> {
+    flatten_to_map(value, Some(max_depth))
+}
+
+fn flatten_to_map(value: &Value, max_depth: Option<usize>) -> Result<HashMap<String, String>, FlattenError> {
+    let mut r = HashMap::<String, String>::new();
+    let mut stack = vec![(String::new(), value, 0usize)];
+    while let Some((key, value, depth)) = stack.pop() {
+        if let Some(max_depth) = max_depth.filter(|max| depth > *max) {
+            return Err(FlattenError::TooDeep { max_depth });
+        }
+        let join = |child: &str| if depth == 0 { child.to_owned() } else { key.clone() + "." + child };
+        let leaf = match value {
+            Value::Object(fields) if !fields.is_empty() => {
+                stack.extend(fields.iter().map(|(k, v)| (join(k), v, depth + 1)));
+                continue;
+            },
+            Value::Array(elements) if !elements.is_empty() => {
+                stack.extend(elements.iter().enumerate().map(|(i, v)| (join(&i.to_string()), v, depth + 1)));
+                continue;
+            },
+            Value::String(s) => s.clone(),
+            _ => value.to_string(),
+        };
+        if r.insert(key.clone(), leaf).is_some() {
+            return Err(FlattenError::KeyCollision { key });
+        }
+    }
+    Ok(r)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn tables() {
+        let value = json!([
+            {"id": 1, "address": {"city": "A", "geo": {"lat": 1.5}}},
+            {"id": 2, "tags": ["x"], "address": {}},
+            {"name": "c", "address": {"city": "C"}},
+        ]);
+        let (columns, rows) = to_table(&value, "/").unwrap();
+        assert_eq!(vec!["address/city", "address/geo/lat", "id", "address", "tags", "name"], columns);
+        assert_eq!(vec![
+            vec![json!("A"), json!(1.5), json!(1), json!(null), json!(null), json!(null)],
+            vec![json!(null), json!(null), json!(2), json!({}), json!(["x"]), json!(null)],
+            vec![json!("C"), json!(null), json!(null), json!(null), json!(null), json!("c")],
+        ], rows);
+
+        assert_eq!(Ok((vec![], vec![vec![]])), to_table(&json!([{}]), "."));
+        assert_eq!(Ok((vec![], vec![])), to_table(&json!([]), "."));
+        assert_eq!(Err(TableError::NotAnArray), to_table(&json!({}), "."));
+        assert_eq!(Err(TableError::NotAnObject { index: 1 }), to_table(&json!([{}, 1]), "."));
+    }
+
+    #[test]
+    fn string_maps() {
+        let value = json!({
+            "db": {"host": "x", "port": 5432, "opts": {"ssl": true, "none": null}},
+            "tags": ["a", {"b": 1.5}],
+            "empty": {},
+            "list": [],
+        });
+        let expected: HashMap<String, String> = [
+            ("db.host", "x"),
+            ("db.port", "5432"),
+            ("db.opts.ssl", "true"),
+            ("db.opts.none", "null"),
+            ("tags.0", "a"),
+            ("tags.1.b", "1.5"),
+            ("empty", "{}"),
+            ("list", "[]"),
+        ].iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        assert_eq!(Ok(expected.clone()), to_string_map(&value));
+        assert_eq!(Ok(expected), to_string_map_bounded(&value, 3));
+        assert_eq!(Err(FlattenError::TooDeep { max_depth: 2 }), to_string_map_bounded(&value, 2));
+
+        assert_eq!(Ok(HashMap::from([("".to_owned(), "s".to_owned())])), to_string_map(&json!("s")));
+        assert_eq!(Err(FlattenError::KeyCollision { key: "a.b".into() }), to_string_map(&json!({"a.b": 1, "a": {"b": 2}})));
+    }
+
+    #[test]
+    fn env_vars() {
+        let value = json!({
+            "db": {"host": "x", "port": 5432, "ssl": true, "user-name": null},
+            "tags": ["a", "b"],
+            "empty": {},
+        });
+        let pairs = |v: &[(&str, &str)]| v.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect::<Vec<_>>();
+        assert_eq!(pairs(&[
+            ("DB_HOST", "x"),
+            ("DB_PORT", "5432"),
+            ("DB_SSL", "true"),
+            ("DB_USER_NAME", ""),
+            ("TAGS", r#"["a","b"]"#),
+        ]), to_env_vars(&value, ""));
+        assert_eq!(pairs(&[("MY_APP_X", "1")]), to_env_vars(&json!({"x": 1}), "my_app_"));
+        assert_eq!(pairs(&[("APP", "1")]), to_env_vars(&json!(1), "app"));
+    }
+}
@@ -0,0 +1,361 @@
+use super::{Result, Value};
+use super::super::Serialize;
+
+/// Serializes a value into compact JSON of at most `max_bytes` bytes,
+/// indicating whether the output was truncated.
+///
+/// When the whole value does not fit, trailing array elements and object
+/// members are dropped, at any depth, so that the output remains valid
+/// JSON. If the value is not an array or object and does not fit, or if
+/// not even an empty container fits, `null` is returned.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!((r#"[1,2]"#.to_owned(), true), json::serialize_bounded(&json!([1, 2, 3]), 6));
+/// ```
+pub fn serialize_bounded(value: &Value, max_bytes: usize) -> (String, bool) {
+    let full = value.to_string();
+    if full.len() <= max_bytes {
+        return (full, false);
+    }
+    (truncate_to(value, max_bytes).map(|(r, _)| r).unwrap_or_else(|| "null".to_owned()), true)
+}
+
+/// Serializes a value into at most `budget` bytes, dropping trailing
+/// elements, and indicates whether any element was dropped.
+/// Returns `None` if not even an empty container fits.
+fn truncate_to(value: &Value, budget: usize) -> Option<(String, bool)> {
+    let full = value.to_string();
+    if full.len() <= budget {
+        return Some((full, false));
+    }
+    let (open, close) = match value {
+        Value::Array(_) => ('[', ']'),
+        Value::Object(_) => ('{', '}'),
+        _ => return None,
+    };
+    if budget < 2 {
+        return None;
+    }
+    let entries: Vec<(Option<&String>, &Value)> = match value {
+        Value::Array(elements) => elements.iter().map(|e| (None, e)).collect(),
+        Value::Object(fields) => fields.iter().map(|(k, v)| (Some(k), v)).collect(),
+        _ => unreachable!(),
+    };
+    let mut r = open.to_string();
+    for (key, element) in entries {
+        let mut head = if r.len() > 1 { ",".to_owned() } else { "".to_owned() };
+        if let Some(key) = key {
+            head.push_str(&Value::String(key.clone()).to_string());
+            head.push(':');
+        }
+        let Some(remaining) = budget.checked_sub(r.len() + head.len() + 1) else {
+            break;
+        };
+        let Some((element, truncated)) = truncate_to(element, remaining) else {
+            break;
+        };
+        r.push_str(&head);
+        r.push_str(&element);
+        if truncated {
+            break;
+        }
+    }
+    r.push(close);
+    Some((r, true))
+}
+
+/// The `PageError` enumeration represents an error that occurred
+/// while serializing a value as pages with [`serialize_pages`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PageError {
+    /// The value is not an array.
+    NotAnArray,
+    /// The page size is zero.
+    ZeroPageSize,
+}
+
+impl std::fmt::Display for PageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnArray => write!(f, "Expected an array to split into pages"),
+            Self::ZeroPageSize => write!(f, "Page size must not be zero"),
+        }
+    }
+}
+
+impl std::error::Error for PageError {}
+
+/// Serializes an array as pages of at most `page_size` elements, each
+/// page being a compact JSON array, such as for streaming a large array
+/// over a network. Only the last page may have fewer elements, and an
+/// empty array produces no pages.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(vec!["[1,2]", "[3]"], json::serialize_pages(&json!([1, 2, 3]), 2).unwrap());
+/// ```
+pub fn serialize_pages(value: &Value, page_size: usize) -> std::result::Result<Vec<String>, PageError> {
+    let elements = value.as_array().ok_or(PageError::NotAnArray)?;
+    if page_size == 0 {
+        return Err(PageError::ZeroPageSize);
+    }
+    Ok(elements.chunks(page_size).map(|page| {
+        let elements: Vec<String> = page.iter().map(|e| e.to_string()).collect();
+        format!("[{}]", elements.join(","))
+    }).collect())
+}
+
+/// Serializes a value into pretty-printed JSON colored with ANSI escape
+/// codes, for command-line tools that dump JSON. Keys are cyan, strings
+/// green, numbers yellow, and `null` and booleans magenta.
+///
+/// If the `NO_COLOR` environment variable is set to a non-empty value,
+/// the output is plain, as [`serialize_pretty`](super::serialize_pretty) returns.
+pub fn serialize_pretty_ansi(value: &Value) -> String {
+    serialize_pretty_ansi_with(value, std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()))
+}
+
+/// Serializes a value as [`serialize_pretty_ansi`] does, with plain
+/// output if `no_color` is `true`.
+fn serialize_pretty_ansi_with(value: &Value, no_color: bool) -> String {
+    if no_color {
+        return serialize_pretty_styled(value, &|_, text| text.to_owned());
+    }
+    serialize_pretty_styled(value, &|kind, text| {
+        let color = match kind {
+            StyleKind::Key => "36",
+            StyleKind::String => "32",
+            StyleKind::Number => "33",
+            StyleKind::Bool | StyleKind::Null => "35",
+        };
+        format!("\u{1b}[{}m{}\u{1b}[0m", color, text)
+    })
+}
+
+/// Serializes a value into compact JSON containing only ASCII characters,
+/// for systems that mangle other bytes. Each non-ASCII character is
+/// escaped as a `\uXXXX` sequence, or as a surrogate pair of them for
+/// characters outside the Basic Multilingual Plane. The output is still
+/// valid JSON that decodes to the same value.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(r#""caf\u00e9""#, json::serialize_ascii(&json!("café")).unwrap());
+/// ```
+pub fn serialize_ascii<T>(value: &T) -> Result<String>
+    where T: ?Sized + Serialize
+{
+    let compact = serde_json::to_string(value)?;
+    let mut r = String::with_capacity(compact.len());
+    for ch in compact.chars() {
+        if ch.is_ascii() {
+            r.push(ch);
+            continue;
+        }
+        for unit in ch.encode_utf16(&mut [0; 2]) {
+            r.push_str(&format!("\\u{:04x}", unit));
+        }
+    }
+    Ok(r)
+}
+
+/// Serializes a value into compact JSON that is safe to embed in a
+/// single-line context, such as an HTTP header value.
+///
+/// Besides the control characters that JSON always escapes, such as
+/// line feeds and carriage returns, the delete character, the C1 control
+/// characters and the Unicode line and paragraph separators are escaped
+/// as `\uXXXX` sequences. The output is still valid JSON that decodes
+/// to the same value.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(r#"{"a":"x\ny\u2028"}"#, json::serialize_header_safe(&json!({"a": "x\ny\u{2028}"})).unwrap());
+/// ```
+pub fn serialize_header_safe(value: &Value) -> Result<String> {
+    let compact = serde_json::to_string(value)?;
+    let mut r = String::with_capacity(compact.len());
+    for ch in compact.chars() {
+        if ch == '\u{7F}' || ('\u{80}'..='\u{9F}').contains(&ch) || ch == '\u{2028}' || ch == '\u{2029}' {
+            r.push_str(&format!("\\u{:04x}", ch as u32));
+        } else {
+            r.push(ch);
+        }
+    }
+    Ok(r)
+}
+
+/// The kind of a token passed to the style callback
+/// of [`serialize_pretty_styled`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StyleKind {
+    /// An object key, including its quotes.
+    Key,
+    /// A string value, including its quotes.
+    String,
+    /// A number value.
+    Number,
+    /// A `true` or `false` value.
+    Bool,
+    /// A `null` value.
+    Null,
+}
+
+/// Serializes a value into pretty-printed JSON, formatted as
+/// [`serialize_pretty`](super::serialize_pretty) does, passing the JSON
+/// text of every key and scalar value through `style`. The callback
+/// may, for example, wrap the text in ANSI color codes.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json, StyleKind};
+/// let style = |kind: StyleKind, text: &str| if kind == StyleKind::Key { format!("<{}>", text) } else { text.to_owned() };
+/// assert_eq!("{\n  <\"a\">: 1\n}", json::serialize_pretty_styled(&json!({"a": 1}), &style));
+/// ```
+pub fn serialize_pretty_styled(value: &Value, style: &dyn Fn(StyleKind, &str) -> String) -> String {
+    let mut r = String::new();
+    write_styled(&mut r, value, 0, style);
+    r
+}
+
+fn write_styled(r: &mut String, value: &Value, depth: usize, style: &dyn Fn(StyleKind, &str) -> String) {
+    let indent = |r: &mut String, depth: usize| r.push_str(&"  ".repeat(depth));
+    match value {
+        Value::Null => r.push_str(&style(StyleKind::Null, "null")),
+        Value::Bool(b) => r.push_str(&style(StyleKind::Bool, &b.to_string())),
+        Value::Number(n) => r.push_str(&style(StyleKind::Number, &n.to_string())),
+        Value::String(_) => r.push_str(&style(StyleKind::String, &value.to_string())),
+        Value::Array(elements) if elements.is_empty() => r.push_str("[]"),
+        Value::Object(fields) if fields.is_empty() => r.push_str("{}"),
+        Value::Array(elements) => {
+            r.push('[');
+            for (i, element) in elements.iter().enumerate() {
+                r.push_str(if i == 0 { "\n" } else { ",\n" });
+                indent(r, depth + 1);
+                write_styled(r, element, depth + 1, style);
+            }
+            r.push('\n');
+            indent(r, depth);
+            r.push(']');
+        },
+        Value::Object(fields) => {
+            r.push('{');
+            for (i, (key, field)) in fields.iter().enumerate() {
+                r.push_str(if i == 0 { "\n" } else { ",\n" });
+                indent(r, depth + 1);
+                r.push_str(&style(StyleKind::Key, &Value::String(key.clone()).to_string()));
+                r.push_str(": ");
+                write_styled(r, field, depth + 1, style);
+            }
+            r.push('\n');
+            indent(r, depth);
+            r.push('}');
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn bounded() {
+        let value = json!({"a": [1, 2, 3], "b": "text"});
+        let full = value.to_string();
+        assert_eq!((full.clone(), false), serialize_bounded(&value, full.len()));
+        assert_eq!((r#"{"a":[1,2,3]}"#.to_owned(), true), serialize_bounded(&value, full.len() - 1));
+        assert_eq!((r#"{"a":[1]}"#.to_owned(), true), serialize_bounded(&value, 10));
+        assert_eq!((r#"{"a":[]}"#.to_owned(), true), serialize_bounded(&value, 8));
+        assert_eq!((r#"{}"#.to_owned(), true), serialize_bounded(&value, 7));
+        assert_eq!(("null".to_owned(), true), serialize_bounded(&value, 1));
+        assert_eq!(("null".to_owned(), true), serialize_bounded(&json!("long text"), 5));
+        for budget in 2..full.len() {
+            let (s, truncated) = serialize_bounded(&value, budget);
+            assert!(truncated && s.len() <= budget);
+            assert!(serde_json::from_str::<Value>(&s).is_ok());
+        }
+    }
+
+    #[test]
+    fn pages() {
+        let value = json!([1, "two", {"three": 3}, [4], null, true, 7]);
+        let pages = serialize_pages(&value, 3).unwrap();
+        assert_eq!(vec![r#"[1,"two",{"three":3}]"#, "[[4],null,true]", "[7]"], pages);
+        let joined: Vec<Value> = pages.iter().flat_map(|p| serde_json::from_str::<Vec<Value>>(p).unwrap()).collect();
+        assert_eq!(value, Value::Array(joined));
+
+        assert_eq!(Ok(vec!["[1,2]".to_owned()]), serialize_pages(&json!([1, 2]), 5));
+        assert_eq!(Ok(vec!["[1]".to_owned(), "[2]".to_owned()]), serialize_pages(&json!([1, 2]), 1));
+        assert_eq!(Ok(vec![]), serialize_pages(&json!([]), 2));
+        assert_eq!(Err(PageError::NotAnArray), serialize_pages(&json!({"a": [1]}), 2));
+        assert_eq!(Err(PageError::ZeroPageSize), serialize_pages(&json!([1]), 0));
+    }
+
+    #[test]
+    fn pretty_styled() {
+        let value = json!({"a": [1, 2.5, {"b": null}], "c": {}, "d": [], "e": "t\"x", "f": true});
+        let identity = |_: StyleKind, text: &str| text.to_owned();
+        assert_eq!(serde_json::to_string_pretty(&value).unwrap(), serialize_pretty_styled(&value, &identity));
+
+        let keys = |kind: StyleKind, text: &str| match kind {
+            StyleKind::Key => format!("\u{1b}[34m{}\u{1b}[0m", text),
+            _ => text.to_owned(),
+        };
+        let s = serialize_pretty_styled(&json!({"k": "v", "n": [null]}), &keys);
+        assert_eq!("{\n  \u{1b}[34m\"k\"\u{1b}[0m: \"v\",\n  \u{1b}[34m\"n\"\u{1b}[0m: [\n    null\n  ]\n}", s);
+
+        let kinds = std::cell::RefCell::new(Vec::<StyleKind>::new());
+        serialize_pretty_styled(&json!(["s", 1, false, null]), &|kind, text| {
+            kinds.borrow_mut().push(kind);
+            text.to_owned()
+        });
+        assert_eq!(vec![StyleKind::String, StyleKind::Number, StyleKind::Bool, StyleKind::Null], kinds.into_inner());
+    }
+
+    #[test]
+    fn pretty_ansi() {
+        let value = json!({"k": ["s", 1, true, null]});
+        let colored = serialize_pretty_ansi_with(&value, false);
+        for code in ["\u{1b}[36m\"k\"", "\u{1b}[32m\"s\"", "\u{1b}[33m1", "\u{1b}[35mtrue", "\u{1b}[35mnull", "\u{1b}[0m"] {
+            assert!(colored.contains(code), "{:?} in {:?}", code, colored);
+        }
+
+        let plain = serialize_pretty_ansi_with(&value, true);
+        assert!(!plain.contains('\u{1b}'));
+        assert_eq!(serde_json::to_string_pretty(&value).unwrap(), plain);
+    }
+
+    #[test]
+    fn header_safe() {
+        let value = json!({"text": "line 1\r\nline 2\u{7F}\u{85}\u{2029}\tend", "n": [1, {"k": "\u{0}"}]});
+        let s = serialize_header_safe(&value).unwrap();
+        assert!(!s.contains(['\r', '\n', '\t', '\u{0}', '\u{7F}', '\u{85}', '\u{2029}']));
+        assert!(s.chars().all(|ch| !ch.is_control()));
+        assert_eq!(r#"{"n":[1,{"k":"\u0000"}],"text":"line 1\r\nline 2\u007f\u0085\u2029\tend"}"#, s);
+        assert_eq!(value, serde_json::from_str::<Value>(&s).unwrap());
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(r#""caf\u00e9""#, serialize_ascii("café").unwrap());
+        let value = json!({"ключ": ["日本", "😀", "plain \"ascii\"\n"], "n": 1.5});
+        let s = serialize_ascii(&value).unwrap();
+        assert!(s.is_ascii());
+        assert_eq!(r#"{"n":1.5,"\u043a\u043b\u044e\u0447":["\u65e5\u672c","\ud83d\ude00","plain \"ascii\"\n"]}"#, s);
+        assert_eq!(value, serde_json::from_str::<Value>(&s).unwrap());
+
+        let plain = json!({"a": [1, true, null, "x~y"]});
+        assert_eq!(plain.to_string(), serialize_ascii(&plain).unwrap());
+    }
+}
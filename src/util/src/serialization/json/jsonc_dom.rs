@@ -0,0 +1,354 @@
+/*!
+Edit JSONC documents while preserving comments and formatting.
+
+JSONC is JSON with `//` line comments, `/* */` block comments and
+trailing commas. A [`Document`] keeps the original text and the
+location of every node in it, so that changing a value only rewrites
+the text of that value.
+
+# Example
+
+```
+use rialight_util::serialization::json::{json, jsonc_dom::Document};
+
+let mut document = Document::parse("{\n    // the port\n    \"port\": 80\n}").unwrap();
+document.set("/port", &json!(8080)).unwrap();
+assert_eq!("{\n    // the port\n    \"port\": 8080\n}", document.to_string());
+```
+*/
+
+use super::lexer::{self, Token};
+use super::{pointer, Map, Value};
+
+/// The `JsoncError` enumeration represents an error that
+/// occurred while parsing or editing a JSONC document.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum JsoncError {
+    /// The text is not valid JSONC.
+    Syntax {
+        offset: usize,
+        message: String,
+    },
+    /// The JSON Pointer does not refer to an existing node,
+    /// or to a member or element that can be added.
+    NotFound {
+        pointer: String,
+    },
+}
+
+impl std::fmt::Display for JsoncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { offset, message } => write!(f, "{} at byte {}", message, offset),
+            Self::NotFound { pointer } => write!(f, "No node at {:?}", pointer),
+        }
+    }
+}
+
+impl std::error::Error for JsoncError {}
+
+/// The `Document` structure represents a parsed JSONC document,
+/// keeping its comments and formatting.
+#[derive(Clone, Debug)]
+pub struct Document {
+    text: String,
+    root: Node,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    start: usize,
+    end: usize,
+    kind: NodeKind,
+}
+
+#[derive(Clone, Debug)]
+enum NodeKind {
+    Scalar(Value),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl Document {
+    /// Parses a JSONC document. Arrays and objects nested more than
+    /// 128 levels deep are a syntax error.
+    pub fn parse(text: &str) -> Result<Self, JsoncError> {
+        let tokens = lexer::tokens_with_comments(text).collect::<Result<Vec<_>, _>>()
+            .map_err(|e| JsoncError::Syntax { offset: e.offset, message: e.message })?;
+        let mut parser = Parser { tokens, index: 0, end: text.len(), depth: 0 };
+        let root = parser.parse_node()?;
+        if parser.index != parser.tokens.len() {
+            return Err(parser.error("Unexpected trailing characters"));
+        }
+        Ok(Self { text: text.to_owned(), root })
+    }
+
+    /// Returns the value at a JSON Pointer, without comments.
+    pub fn get(&self, pointer: &str) -> Option<Value> {
+        Some(self.find(&pointer::parse(pointer)?)?.to_value())
+    }
+
+    /// Replaces the value at a JSON Pointer, keeping the rest of the
+    /// text intact. A missing object member is added after the last
+    /// member, and an array element is appended if the last reference
+    /// token is the array length or `-`. The new value is written
+    /// as compact JSON.
+    pub fn set(&mut self, pointer: &str, value: &Value) -> Result<(), JsoncError> {
+        let not_found = || JsoncError::NotFound { pointer: pointer.to_owned() };
+        let mut tokens = pointer::parse(pointer).ok_or_else(not_found)?;
+        let value_text = value.to_string();
+        let Some(last) = tokens.pop() else {
+            return self.replace(self.root.start..self.root.end, &value_text);
+        };
+        let parent = self.find(&tokens).ok_or_else(not_found)?;
+        let (range, insertion) = match &parent.kind {
+            NodeKind::Object(members) => match members.iter().find(|(k, _)| *k == last) {
+                Some((_, node)) => (node.start..node.end, value_text),
+                None => {
+                    let member = Value::String(last).to_string() + ": " + &value_text;
+                    match members.last() {
+                        Some((_, node)) => (node.end..node.end, ", ".to_owned() + &member),
+                        None => (parent.start + 1..parent.start + 1, member),
+                    }
+                },
+            },
+            NodeKind::Array(elements) => {
+                let index = if last == "-" { elements.len() } else { last.parse::<usize>().map_err(|_| not_found())? };
+                match (elements.get(index), elements.last()) {
+                    (Some(node), _) => (node.start..node.end, value_text),
+                    (None, Some(node)) if index == elements.len() => (node.end..node.end, ", ".to_owned() + &value_text),
+                    (None, None) if index == 0 => (parent.start + 1..parent.start + 1, value_text),
+                    _ => return Err(not_found()),
+                }
+            },
+            NodeKind::Scalar(_) => return Err(not_found()),
+        };
+        self.replace(range, &insertion)
+    }
+
+    fn find(&self, tokens: &[String]) -> Option<&Node> {
+        let mut node = &self.root;
+        for token in tokens {
+            node = match &node.kind {
+                NodeKind::Object(members) => &members.iter().find(|(k, _)| k == token)?.1,
+                NodeKind::Array(elements) => elements.get(token.parse::<usize>().ok()?)?,
+                NodeKind::Scalar(_) => return None,
+            };
+        }
+        Some(node)
+    }
+
+    fn replace(&mut self, range: std::ops::Range<usize>, replacement: &str) -> Result<(), JsoncError> {
+        let mut text = self.text.clone();
+        text.replace_range(range, replacement);
+        *self = Self::parse(&text)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl Node {
+    fn to_value(&self) -> Value {
+        match &self.kind {
+            NodeKind::Scalar(value) => value.clone(),
+            NodeKind::Array(elements) => Value::Array(elements.iter().map(|e| e.to_value()).collect()),
+            NodeKind::Object(members) => {
+                let mut r = Map::new();
+                for (key, node) in members {
+                    r.insert(key.clone(), node.to_value());
+                }
+                Value::Object(r)
+            },
+        }
+    }
+}
+
+/// The maximum nesting depth of arrays and objects in a document,
+/// matching the limit of `serde_json`.
+const MAX_DEPTH: usize = 128;
+
+struct Parser {
+    tokens: Vec<(Token, std::ops::Range<usize>)>,
+    /// The index of the next token.
+    index: usize,
+    /// The length of the text.
+    end: usize,
+    /// The number of arrays and objects enclosing the current node.
+    depth: usize,
+}
+
+impl Parser {
+    /// Builds a syntax error at the next token, or at the end of the
+    /// text if there are no more tokens.
+    fn error(&self, message: &str) -> JsoncError {
+        let offset = self.tokens.get(self.index).map_or(self.end, |(_, span)| span.start);
+        JsoncError::Syntax { offset, message: message.to_owned() }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn parse_node(&mut self) -> Result<Node, JsoncError> {
+        let Some((token, span)) = self.tokens.get(self.index).cloned() else {
+            return Err(self.error("Unexpected end of input"));
+        };
+        let kind = match token {
+            Token::BeginObject | Token::BeginArray => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error("Exceeded the maximum nesting depth"));
+                }
+                self.depth += 1;
+                self.index += 1;
+                let kind = if token == Token::BeginObject { self.parse_object()? } else { self.parse_array()? };
+                self.depth -= 1;
+                kind
+            },
+            Token::String(string) => self.parse_scalar(Value::String(string)),
+            Token::Number(number) => self.parse_scalar(Value::Number(number)),
+            Token::Bool(boolean) => self.parse_scalar(Value::Bool(boolean)),
+            Token::Null => self.parse_scalar(Value::Null),
+            _ => return Err(self.error("Expected a value")),
+        };
+        Ok(Node { start: span.start, end: self.tokens[self.index - 1].1.end, kind })
+    }
+
+    fn parse_scalar(&mut self, value: Value) -> NodeKind {
+        self.index += 1;
+        NodeKind::Scalar(value)
+    }
+
+    /// Parses the elements of a container up to its closing token,
+    /// allowing a trailing comma.
+    fn parse_elements<F>(&mut self, close: Token, mut parse_element: F) -> Result<(), JsoncError>
+        where F: FnMut(&mut Self) -> Result<(), JsoncError>
+    {
+        loop {
+            if self.peek() == Some(&close) {
+                self.index += 1;
+                return Ok(());
+            }
+            parse_element(self)?;
+            match self.peek() {
+                Some(Token::Comma) => self.index += 1,
+                Some(token) if *token == close => {},
+                _ => return Err(self.error("Expected a comma or a closing bracket")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<NodeKind, JsoncError> {
+        let mut elements = Vec::<Node>::new();
+        self.parse_elements(Token::EndArray, |parser| {
+            elements.push(parser.parse_node()?);
+            Ok(())
+        })?;
+        Ok(NodeKind::Array(elements))
+    }
+
+    fn parse_object(&mut self) -> Result<NodeKind, JsoncError> {
+        let mut members = Vec::<(String, Node)>::new();
+        self.parse_elements(Token::EndObject, |parser| {
+            let Some(Token::String(key)) = parser.peek().cloned() else {
+                return Err(parser.error("Expected a string key"));
+            };
+            parser.index += 1;
+            if parser.peek() != Some(&Token::Colon) {
+                return Err(parser.error("Expected a colon"));
+            }
+            parser.index += 1;
+            let node = parser.parse_node()?;
+            members.retain(|(k, _)| *k != key);
+            members.push((key, node));
+            Ok(())
+        })?;
+        Ok(NodeKind::Object(members))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    const TEXT: &str = r#"{
+    // server settings
+    "server": {
+        "host": "localhost", /* default host */
+        "port": 80,
+    },
+    /* enabled features */
+    "features": ["a", "b"], // trailing
+}"#;
+
+    #[test]
+    fn modify_value() {
+        let mut document = Document::parse(TEXT).unwrap();
+        assert_eq!(Some(json!(80)), document.get("/server/port"));
+        assert_eq!(Some(json!("b")), document.get("/features/1"));
+        assert_eq!(None, document.get("/missing"));
+
+        document.set("/server/port", &json!(8080)).unwrap();
+        assert_eq!(TEXT.replace("\"port\": 80", "\"port\": 8080"), document.to_string());
+        assert_eq!(Some(json!({"host": "localhost", "port": 8080})), document.get("/server"));
+    }
+
+    #[test]
+    fn add_values() {
+        let mut document = Document::parse(TEXT).unwrap();
+        document.set("/server/tls", &json!(true)).unwrap();
+        document.set("/features/-", &json!("c")).unwrap();
+        document.set("/empty", &json!({})).unwrap();
+        document.set("/empty/x", &json!([1])).unwrap();
+        document.set("/empty/x/1", &json!(2)).unwrap();
+        let text = document.to_string();
+        assert!(text.contains("// server settings") && text.contains("/* default host */") && text.contains("// trailing"));
+        assert!(text.contains(r#""port": 80, "tls": true,"#));
+        assert!(text.contains(r#"["a", "b", "c"]"#));
+        assert_eq!(Some(json!({"x": [1, 2]})), document.get("/empty"));
+
+        assert_eq!(Err(JsoncError::NotFound { pointer: "/nope/x".into() }), document.set("/nope/x", &json!(1)));
+        assert!(document.set("/features/9", &json!(1)).is_err());
+        assert!(document.set("/server/port/x", &json!(1)).is_err());
+    }
+
+    #[test]
+    fn replace_root_and_errors() {
+        let mut document = Document::parse("// c\n[1] /* d */").unwrap();
+        document.set("", &json!({"a": 1})).unwrap();
+        assert_eq!("// c\n{\"a\":1} /* d */", document.to_string());
+        assert!(Document::parse("{\"a\": }").is_err());
+        assert!(Document::parse("[1] 2").is_err());
+        assert!(Document::parse("[1 /* x").is_err());
+        assert!(Document::parse("\"a").is_err());
+        assert_eq!(
+            Err(JsoncError::Syntax { offset: 5, message: "Expected a colon".to_owned() }),
+            Document::parse("{\"a\" 1}").map(|_| ()),
+        );
+        assert_eq!(
+            Err(JsoncError::Syntax { offset: 6, message: "Unterminated block comment".to_owned() }),
+            Document::parse("[1, 2 /* x").map(|_| ()),
+        );
+        assert_eq!(
+            Err(JsoncError::Syntax { offset: 3, message: "Unexpected end of input".to_owned() }),
+            Document::parse("[1,").map(|_| ()),
+        );
+    }
+
+    #[test]
+    fn nesting_depth() {
+        let nested = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        assert!(Document::parse(&nested).is_ok());
+        let nested = format!("{}{{}}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert_eq!(
+            Err(JsoncError::Syntax { offset: MAX_DEPTH, message: "Exceeded the maximum nesting depth".to_owned() }),
+            Document::parse(&nested).map(|_| ()),
+        );
+        assert!(matches!(Document::parse(&"[".repeat(200_000)), Err(JsoncError::Syntax { .. })));
+    }
+}
@@ -0,0 +1,278 @@
+use super::{pointer, Map, Number, Value};
+
+/// The `OverrideError` enumeration represents an error
+/// that occurred while applying an override with [`apply_overrides`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum OverrideError {
+    /// The key path is empty or contains an empty segment.
+    EmptySegment {
+        key: String,
+    },
+    /// The key path traverses a value that is not an object.
+    NotAnObject {
+        key: String,
+        segment: String,
+    },
+}
+
+impl std::fmt::Display for OverrideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptySegment { key } => write!(f, "Override key {:?} contains an empty segment", key),
+            Self::NotAnObject { key, segment } => write!(f, "Override key {:?} traverses a non-object value at {:?}", key, segment),
+        }
+    }
+}
+
+impl std::error::Error for OverrideError {}
+
+/// Applies overrides given as key path and value strings, such as
+/// from a `--set a.b.c=5` command-line option, to a JSON value.
+///
+/// Each key path is split by `sep` and any missing intermediate
+/// object is created. The type of each value is inferred from
+/// its string: `true` and `false` become booleans, numeric strings
+/// become numbers and anything else becomes a string.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut value = json!({});
+/// json::apply_overrides(&mut value, &[("a.b.c", "5")], '.').unwrap();
+/// assert_eq!(json!({"a": {"b": {"c": 5}}}), value);
+/// ```
+pub fn apply_overrides(value: &mut Value, overrides: &[(&str, &str)], sep: char) -> Result<(), OverrideError> {
+    for (key, string) in overrides {
+        let segments: Vec<&str> = key.split(sep).collect();
+        if segments.iter().any(|s| s.is_empty()) {
+            return Err(OverrideError::EmptySegment { key: (*key).to_owned() });
+        }
+        let mut target = &mut *value;
+        for (i, segment) in segments.iter().enumerate() {
+            let Value::Object(map) = target else {
+                let segment = if i == 0 { "" } else { segments[i - 1] };
+                return Err(OverrideError::NotAnObject { key: (*key).to_owned(), segment: segment.to_owned() });
+            };
+            if i == segments.len() - 1 {
+                map.insert((*segment).to_owned(), infer_value(string));
+                break;
+            }
+            target = map.entry(*segment).or_insert_with(|| Value::Object(Map::new()));
+        }
+    }
+    Ok(())
+}
+
+/// Indicates how conflicting keys are handled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ConflictPolicy {
+    /// The last conflicting key replaces any previous value.
+    #[default]
+    LastWins,
+    /// A conflicting key causes an error.
+    Error,
+}
+
+/// The `KeyConflictError` structure represents an error that occurred
+/// because a key conflicts with a previously given key.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeyConflictError {
+    /// The conflicting key.
+    pub key: String,
+}
+
+impl std::fmt::Display for KeyConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key {:?} conflicts with a previous key", self.key)
+    }
+}
+
+impl std::error::Error for KeyConflictError {}
+
+/// Builds a nested object from pairs of key paths and values,
+/// splitting each key path by `sep`. Conflicting keys, such as
+/// `a` and `a.b`, are resolved by letting the last pair win.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let value = json::from_pairs([("a.b".to_owned(), json!(1)), ("a.c".to_owned(), json!(2))], '.');
+/// assert_eq!(json!({"a": {"b": 1, "c": 2}}), value);
+/// ```
+pub fn from_pairs<I>(pairs: I, sep: char) -> Value
+    where I: IntoIterator<Item = (String, Value)>
+{
+    from_pairs_with_policy(pairs, sep, ConflictPolicy::LastWins).unwrap()
+}
+
+/// Builds a nested object from pairs of key paths and values,
+/// handling conflicting keys according to `policy`.
+/// See [`from_pairs`].
+pub fn from_pairs_with_policy<I>(pairs: I, sep: char, policy: ConflictPolicy) -> Result<Value, KeyConflictError>
+    where I: IntoIterator<Item = (String, Value)>
+{
+    let mut r = Map::new();
+    for (key, value) in pairs {
+        let segments: Vec<&str> = key.split(sep).collect();
+        let mut target = &mut r;
+        for segment in &segments[..segments.len() - 1] {
+            let entry = target.entry(*segment).or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                if policy == ConflictPolicy::Error {
+                    return Err(KeyConflictError { key });
+                }
+                *entry = Value::Object(Map::new());
+            }
+            target = entry.as_object_mut().unwrap();
+        }
+        let last = segments.last().unwrap();
+        if policy == ConflictPolicy::Error && target.contains_key(*last) {
+            return Err(KeyConflictError { key });
+        }
+        target.insert((*last).to_owned(), value);
+    }
+    Ok(Value::Object(r))
+}
+
+/// Converts a key path split by `sep`, such as `a.b.c`, into a JSON
+/// Pointer ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)) such as
+/// `/a/b/c`, escaping `~` and `/` in keys.
+///
+/// In the key path, a backslash (`\`) makes the next character part of
+/// the key, so `a\.b` is the single key `a.b` when `sep` is `.`. An
+/// empty key path refers to the whole value and converts to an empty
+/// pointer.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json;
+/// assert_eq!("/a/b~1c/d.e", json::dotted_to_pointer(r"a.b/c.d\.e", '.'));
+/// ```
+pub fn dotted_to_pointer(dotted: &str, sep: char) -> String {
+    if dotted.is_empty() {
+        return "".to_owned();
+    }
+    let mut r = String::new();
+    let mut key = String::new();
+    let mut chars = dotted.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            key.extend(chars.next());
+        } else if ch == sep {
+            r = pointer::push_token(&r, &std::mem::take(&mut key));
+        } else {
+            key.push(ch);
+        }
+    }
+    pointer::push_token(&r, &key)
+}
+
+/// Converts a JSON Pointer into a key path split by `sep`, the reverse
+/// of [`dotted_to_pointer`]. A separator or backslash within a key is
+/// escaped with a backslash (`\`).
+///
+/// Returns `None` if the pointer is neither empty nor starts with `/`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json;
+/// assert_eq!(Some(r"a.b/c.d\.e".to_owned()), json::pointer_to_dotted("/a/b~1c/d.e", '.'));
+/// ```
+pub fn pointer_to_dotted(pointer: &str, sep: char) -> Option<String> {
+    let keys: Vec<String> = pointer::parse(pointer)?.into_iter().map(|key| {
+        let mut r = String::new();
+        for ch in key.chars() {
+            if ch == sep || ch == '\\' {
+                r.push('\\');
+            }
+            r.push(ch);
+        }
+        r
+    }).collect();
+    Some(keys.join(&sep.to_string()))
+}
+
+/// Infers a boolean, number or string value from a string.
+pub(crate) fn infer_value(string: &str) -> Value {
+    match string {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {},
+    }
+    if let Ok(n) = string.parse::<i64>() {
+        return Value::Number(n.into());
+    }
+    if let Ok(n) = string.parse::<u64>() {
+        return Value::Number(n.into());
+    }
+    if let Some(n) = string.parse::<f64>().ok().and_then(Number::from_f64) {
+        return Value::Number(n);
+    }
+    Value::String(string.to_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn overrides() {
+        let mut value = json!({"a": {"x": "y"}});
+        apply_overrides(&mut value, &[("a.b.c", "5"), ("d", "true"), ("e", "1.5"), ("f", "text")], '.').unwrap();
+        assert_eq!(json!({"a": {"x": "y", "b": {"c": 5}}, "d": true, "e": 1.5, "f": "text"}), value);
+
+        let mut value = json!({"a": 1});
+        assert_eq!(Err(OverrideError::NotAnObject { key: "a/b".into(), segment: "a".into() }), apply_overrides(&mut value, &[("a/b", "false")], '/'));
+        assert_eq!(Err(OverrideError::EmptySegment { key: "a..b".into() }), apply_overrides(&mut value, &[("a..b", "false")], '.'));
+    }
+
+    #[test]
+    fn dotted_and_pointer() {
+        assert_eq!("/a/b/c", dotted_to_pointer("a.b.c", '.'));
+        assert_eq!("/a.b/c", dotted_to_pointer(r"a\.b.c", '.'));
+        assert_eq!("/a~0b/c~1d", dotted_to_pointer("a~b.c/d", '.'));
+        assert_eq!("/a/b.c", dotted_to_pointer("a/b.c", '/'));
+        assert_eq!(r"/a\b/", dotted_to_pointer(r"a\\b.", '.'));
+        assert_eq!("", dotted_to_pointer("", '.'));
+
+        assert_eq!(Some("a.b.c".to_owned()), pointer_to_dotted("/a/b/c", '.'));
+        assert_eq!(Some(r"a\.b.c".to_owned()), pointer_to_dotted("/a.b/c", '.'));
+        assert_eq!(Some("".to_owned()), pointer_to_dotted("", '.'));
+        assert_eq!(None, pointer_to_dotted("a/b", '.'));
+
+        for dotted in ["a.b.c", r"a\.b.c", r"x\\y.z", "a~b.c/d", "single", r"trailing\."] {
+            assert_eq!(Some(dotted.to_owned()), pointer_to_dotted(&dotted_to_pointer(dotted, '.'), '.'));
+        }
+        for pointer in ["/a/b", "/a.b/c~1d", "/~0/x", r"/a\b"] {
+            assert_eq!(pointer, dotted_to_pointer(&pointer_to_dotted(pointer, '.').unwrap(), '.'));
+        }
+
+        let value = json!({"a.b": {"c": 1}});
+        assert_eq!(Some(&json!(1)), value.pointer(&dotted_to_pointer(r"a\.b.c", '.')));
+    }
+
+    #[test]
+    fn pairs() {
+        let pairs = || vec![
+            ("db.host".to_owned(), json!("x")),
+            ("db.port".to_owned(), json!(5432)),
+            ("name".to_owned(), json!("app")),
+        ];
+        assert_eq!(json!({"db": {"host": "x", "port": 5432}, "name": "app"}), from_pairs(pairs(), '.'));
+        assert_eq!(Ok(json!({"db": {"host": "x", "port": 5432}, "name": "app"})), from_pairs_with_policy(pairs(), '.', ConflictPolicy::Error));
+
+        let conflicting = || vec![
+            ("a".to_owned(), json!(1)),
+            ("a_b".to_owned(), json!(2)),
+            ("a_b".to_owned(), json!(3)),
+        ];
+        assert_eq!(json!({"a": {"b": 3}}), from_pairs(conflicting(), '_'));
+        assert_eq!(Err(KeyConflictError { key: "a_b".into() }), from_pairs_with_policy(conflicting(), '_', ConflictPolicy::Error));
+        assert_eq!(Err(KeyConflictError { key: "a".into() }), from_pairs_with_policy([("a".to_owned(), json!(1)), ("a".to_owned(), json!(2))], '.', ConflictPolicy::Error));
+    }
+}
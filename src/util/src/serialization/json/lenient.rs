@@ -0,0 +1,170 @@
+use super::lexer::{self, Token};
+use super::Result;
+use super::super::Deserialize;
+use super::super::generic_deserialization::DeserializeOwned;
+
+/// Deserializes a JSON string into a value, ignoring a leading
+/// byte order mark (`U+FEFF`).
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json;
+/// assert_eq!(vec![1], json::deserialize_tolerant::<Vec<i32>>("\u{FEFF}[1]").unwrap());
+/// ```
+pub fn deserialize_tolerant<'a, T>(string: &'a str) -> Result<T>
+    where T: Deserialize<'a>
+{
+    serde_json::from_str(string.strip_prefix('\u{FEFF}').unwrap_or(string))
+}
+
+/// Deserializes JSON given as a sequence of bytes into a value,
+/// ignoring a leading UTF-8 byte order mark (`EF BB BF`).
+pub fn deserialize_from_slice_tolerant<'a, T>(slice: &'a [u8]) -> Result<T>
+    where T: Deserialize<'a>
+{
+    serde_json::from_slice(slice.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(slice))
+}
+
+/// The `CodecError` enumeration represents an error that occurred
+/// while decoding JSON text of any encoding.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CodecError {
+    /// The bytes are not valid UTF-8.
+    InvalidUtf8,
+    /// The bytes are not valid UTF-16.
+    InvalidUtf16,
+    /// The decoded text is not valid JSON for the target type.
+    Json {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for CodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidUtf8 => write!(f, "Invalid UTF-8 text"),
+            Self::InvalidUtf16 => write!(f, "Invalid UTF-16 text"),
+            Self::Json { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Deserializes JSON given as a sequence of bytes into a value,
+/// detecting UTF-8, UTF-16LE or UTF-16BE text by its byte order mark.
+/// Bytes without a byte order mark are decoded as UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json;
+/// let bytes: Vec<u8> = [0xFF, 0xFE].into_iter().chain("[1]".encode_utf16().flat_map(u16::to_le_bytes)).collect();
+/// assert_eq!(vec![1], json::deserialize_any_encoding::<Vec<i32>>(&bytes).unwrap());
+/// ```
+pub fn deserialize_any_encoding<T>(bytes: &[u8]) -> std::result::Result<T, CodecError>
+    where T: DeserializeOwned
+{
+    let text = if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        decode_utf16(rest, u16::from_le_bytes)?
+    } else if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        decode_utf16(rest, u16::from_be_bytes)?
+    } else {
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        String::from_utf8(bytes.to_vec()).map_err(|_| CodecError::InvalidUtf8)?
+    };
+    serde_json::from_str(&text).map_err(|error| CodecError::Json { message: error.to_string() })
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> std::result::Result<String, CodecError> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(CodecError::InvalidUtf16);
+    }
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units).collect::<std::result::Result<String, _>>().map_err(|_| CodecError::InvalidUtf16)
+}
+
+/// Deserializes a JSON string that may contain a trailing comma after
+/// the last element before a closing `}` or `]`. Commas inside strings
+/// are left untouched and the input is otherwise parsed as strict JSON.
+///
+/// The type `T` is not allowed to borrow from the input, since the
+/// input is rewritten before being parsed. Removed commas are
+/// replaced by spaces, so error positions match the original input.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json, Value};
+/// let value: Value = json::deserialize_lenient_commas(r#"{"a": [1, 2,], }"#).unwrap();
+/// assert_eq!(json!({"a": [1, 2]}), value);
+/// ```
+pub fn deserialize_lenient_commas<T>(string: &str) -> Result<T>
+    where T: DeserializeOwned
+{
+    serde_json::from_str(&remove_trailing_commas(string))
+}
+
+fn remove_trailing_commas(string: &str) -> String {
+    // on a lexical error, the string is left as it is for the parser to report
+    let Ok(tokens) = lexer::tokens(string).collect::<std::result::Result<Vec<_>, _>>() else {
+        return string.to_owned();
+    };
+    let mut r = string.to_owned();
+    for (i, (token, span)) in tokens.iter().enumerate() {
+        if *token != Token::Comma || i == 0 {
+            continue;
+        }
+        let follows_element = !matches!(tokens[i - 1].0, Token::BeginArray | Token::BeginObject | Token::Comma);
+        let closes = matches!(tokens.get(i + 1), Some((Token::EndArray | Token::EndObject, _)));
+        if follows_element && closes {
+            r.replace_range(span.clone(), " ");
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::{json, Value};
+
+    #[test]
+    fn byte_order_mark() {
+        assert_eq!(json!({"a": 1}), deserialize_tolerant::<Value>("\u{FEFF}{\"a\": 1}").unwrap());
+        assert_eq!(json!({"a": 1}), deserialize_tolerant::<Value>("{\"a\": 1}").unwrap());
+        assert_eq!("x", deserialize_tolerant::<&str>("\u{FEFF}\"x\"").unwrap());
+        assert!(deserialize_tolerant::<Value>("\u{FEFF}\u{FEFF}1").is_err());
+        assert_eq!(json!([true]), deserialize_from_slice_tolerant::<Value>(b"\xEF\xBB\xBF[true]").unwrap());
+        assert_eq!(json!([true]), deserialize_from_slice_tolerant::<Value>(b"[true]").unwrap());
+        assert!(super::super::deserialize::<Value>("\u{FEFF}1").is_err());
+    }
+
+    #[test]
+    fn any_encoding() {
+        let text = r#"{"name": "Zoë", "n": [1, 2]}"#;
+        let expected = json!({"name": "Zoë", "n": [1, 2]});
+        let utf16_le: Vec<u8> = [0xFF, 0xFE].into_iter().chain(text.encode_utf16().flat_map(u16::to_le_bytes)).collect();
+        let utf16_be: Vec<u8> = [0xFE, 0xFF].into_iter().chain(text.encode_utf16().flat_map(u16::to_be_bytes)).collect();
+        let utf8_bom: Vec<u8> = b"\xEF\xBB\xBF".iter().chain(text.as_bytes()).copied().collect();
+        assert_eq!(expected, deserialize_any_encoding::<Value>(&utf16_le).unwrap());
+        assert_eq!(expected, deserialize_any_encoding::<Value>(&utf16_be).unwrap());
+        assert_eq!(expected, deserialize_any_encoding::<Value>(&utf8_bom).unwrap());
+        assert_eq!(expected, deserialize_any_encoding::<Value>(text.as_bytes()).unwrap());
+
+        assert_eq!(Err(CodecError::InvalidUtf16), deserialize_any_encoding::<Value>(&utf16_le[..utf16_le.len() - 1]));
+        assert_eq!(Err(CodecError::InvalidUtf16), deserialize_any_encoding::<Value>(&[0xFF, 0xFE, 0x00, 0xD8]));
+        assert_eq!(Err(CodecError::InvalidUtf8), deserialize_any_encoding::<Value>(&[b'"', 0xC3, b'"']));
+        assert!(matches!(deserialize_any_encoding::<Value>(b"[1,"), Err(CodecError::Json { .. })));
+    }
+
+    #[test]
+    fn trailing_commas() {
+        assert_eq!(json!({"a": 1, "b": 2}), deserialize_lenient_commas::<Value>("{\"a\": 1, \"b\": 2,\n}").unwrap());
+        assert_eq!(json!([1, [2]]), deserialize_lenient_commas::<Value>("[1, [2,] ,\t]").unwrap());
+        assert_eq!(json!({"s": "x,}", "t": "\",]"}), deserialize_lenient_commas::<Value>(r#"{"s": "x,}", "t": "\",]",}"#).unwrap());
+        assert!(deserialize_lenient_commas::<Value>("[1,,]").is_err());
+        assert!(deserialize_lenient_commas::<Value>("[,]").is_err());
+    }
+}
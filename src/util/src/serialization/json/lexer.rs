@@ -0,0 +1,229 @@
+/*!
+Tokenize JSON text, for building custom parsers and linters.
+
+# Example
+
+```
+use rialight_util::serialization::json::lexer::{self, Token};
+
+let tokens: Vec<(Token, std::ops::Range<usize>)> = lexer::tokens(r#"{"a": 1}"#).collect::<Result<_, _>>().unwrap();
+assert_eq!((Token::String("a".into()), 1..4), tokens[1]);
+assert_eq!(5, tokens.len());
+```
+*/
+
+use super::Number;
+
+/// A JSON token.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Token {
+    /// `{`
+    BeginObject,
+    /// `}`
+    EndObject,
+    /// `[`
+    BeginArray,
+    /// `]`
+    EndArray,
+    /// `:`
+    Colon,
+    /// `,`
+    Comma,
+    /// A string, with its escape sequences decoded.
+    String(String),
+    /// A number, with its value parsed.
+    Number(Number),
+    /// `true` or `false`.
+    Bool(bool),
+    /// `null`
+    Null,
+}
+
+/// The `LexError` structure represents an error that occurred
+/// because the input contains an invalid token.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LexError {
+    /// The byte offset at which the invalid token starts.
+    pub offset: usize,
+    /// A description of the error, such as `Unterminated string`.
+    pub message: String,
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// Splits JSON text into tokens along with their byte spans, skipping
+/// whitespace. Only tokens are checked: the order of tokens is not
+/// validated, so that the output of `[1 2` is also tokenized.
+///
+/// The iterator ends after the first error.
+pub fn tokens(input: &str) -> impl Iterator<Item = Result<(Token, std::ops::Range<usize>), LexError>> + '_ {
+    Lexer { input, offset: 0, failed: false, comments: false }
+}
+
+/// Splits JSONC text into tokens as [`tokens`] does, additionally
+/// skipping `//` line comments and `/* */` block comments like
+/// whitespace. An unterminated block comment is an error.
+pub fn tokens_with_comments(input: &str) -> impl Iterator<Item = Result<(Token, std::ops::Range<usize>), LexError>> + '_ {
+    Lexer { input, offset: 0, failed: false, comments: true }
+}
+
+struct Lexer<'a> {
+    input: &'a str,
+    offset: usize,
+    failed: bool,
+    /// Whether comments are skipped like whitespace.
+    comments: bool,
+}
+
+impl<'a> Lexer<'a> {
+    fn error(&mut self, start: usize, message: &str) -> LexError {
+        self.failed = true;
+        LexError { offset: start, message: message.to_owned() }
+    }
+
+    /// Finds the start of the next token, skipping whitespace
+    /// and, if enabled, comments.
+    fn skip_trivia(&mut self) -> Result<usize, LexError> {
+        let mut start = self.offset;
+        loop {
+            let rest = &self.input[start..];
+            let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+            start += rest.len() - trimmed.len();
+            if !self.comments {
+                return Ok(start);
+            }
+            if trimmed.starts_with("//") {
+                start += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                let end = comment.find("*/").ok_or_else(|| self.error(start, "Unterminated block comment"))?;
+                start += end + 4;
+            } else {
+                return Ok(start);
+            }
+        }
+    }
+
+    fn lex(&mut self, start: usize) -> Result<Token, LexError> {
+        let rest = &self.input[start..];
+        let punctuation = match rest.as_bytes()[0] {
+            b'{' => Some(Token::BeginObject),
+            b'}' => Some(Token::EndObject),
+            b'[' => Some(Token::BeginArray),
+            b']' => Some(Token::EndArray),
+            b':' => Some(Token::Colon),
+            b',' => Some(Token::Comma),
+            _ => None,
+        };
+        if let Some(token) = punctuation {
+            self.offset = start + 1;
+            return Ok(token);
+        }
+        for (literal, token) in [("true", Token::Bool(true)), ("false", Token::Bool(false)), ("null", Token::Null)] {
+            if rest.starts_with(literal) && !rest[literal.len()..].starts_with(|ch: char| ch.is_ascii_alphanumeric()) {
+                self.offset = start + literal.len();
+                return Ok(token);
+            }
+        }
+        if rest.starts_with('"') {
+            let bytes = rest.as_bytes();
+            let mut i = 1;
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            if i >= bytes.len() {
+                return Err(self.error(start, "Unterminated string"));
+            }
+            let string = serde_json::from_str::<String>(&rest[..=i]).map_err(|_| self.error(start, "Invalid string"))?;
+            self.offset = start + i + 1;
+            return Ok(Token::String(string));
+        }
+        if rest.starts_with(|ch: char| ch == '-' || ch.is_ascii_digit()) {
+            let len = rest.find(|ch: char| !(ch.is_ascii_digit() || "+-.eE".contains(ch))).unwrap_or(rest.len());
+            let number = serde_json::from_str::<Number>(&rest[..len]).map_err(|_| self.error(start, "Invalid number"))?;
+            self.offset = start + len;
+            return Ok(Token::Number(number));
+        }
+        Err(self.error(start, "Unexpected character"))
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<(Token, std::ops::Range<usize>), LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let start = match self.skip_trivia() {
+            Ok(start) => start,
+            Err(error) => return Some(Err(error)),
+        };
+        if start == self.input.len() {
+            return None;
+        }
+        Some(self.lex(start).map(|token| (token, start..self.offset)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokenize() {
+        let input = "{\"k\\n\": [1, -2.5e3, true, null],\n \"b\": false}";
+        let tokens: Vec<_> = tokens(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(vec![
+            (Token::BeginObject, 0..1),
+            (Token::String("k\n".into()), 1..6),
+            (Token::Colon, 6..7),
+            (Token::BeginArray, 8..9),
+            (Token::Number(1.into()), 9..10),
+            (Token::Comma, 10..11),
+            (Token::Number(Number::from_f64(-2500.0).unwrap()), 12..18),
+            (Token::Comma, 18..19),
+            (Token::Bool(true), 20..24),
+            (Token::Comma, 24..25),
+            (Token::Null, 26..30),
+            (Token::EndArray, 30..31),
+            (Token::Comma, 31..32),
+            (Token::String("b".into()), 34..37),
+            (Token::Colon, 37..38),
+            (Token::Bool(false), 39..44),
+            (Token::EndObject, 44..45),
+        ], tokens);
+        assert_eq!(0, super::tokens("  \n").count());
+    }
+
+    #[test]
+    fn errors() {
+        let r: Vec<_> = tokens("[1, @]").collect();
+        assert_eq!(4, r.len());
+        assert_eq!(Err(LexError { offset: 4, message: "Unexpected character".into() }), r[3]);
+        assert_eq!(Some(Err(LexError { offset: 0, message: "Unterminated string".into() })), tokens("\"abc").next());
+        assert!(tokens("01").next().unwrap().is_err());
+        assert!(tokens("truex").next().unwrap().is_err());
+        assert!(tokens("\"\\q\"").next().unwrap().is_err());
+    }
+
+    #[test]
+    fn comments() {
+        let input = "// a\n[1, /* b */ 2] // c";
+        let tokens: Vec<_> = tokens_with_comments(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(vec![
+            (Token::BeginArray, 5..6),
+            (Token::Number(1.into()), 6..7),
+            (Token::Comma, 7..8),
+            (Token::Number(2.into()), 17..18),
+            (Token::EndArray, 18..19),
+        ], tokens);
+        assert_eq!(Some(Err(LexError { offset: 3, message: "Unterminated block comment".into() })), tokens_with_comments("[1 /* x").nth(2));
+        assert!(super::tokens("// a").next().unwrap().is_err());
+    }
+}
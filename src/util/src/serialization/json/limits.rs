@@ -0,0 +1,151 @@
+use super::lexer::{self, Token};
+use super::super::generic_deserialization::DeserializeOwned;
+
+/// Limits for [`deserialize_with_limits`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Limits {
+    /// The maximum nesting depth of a value. The root is at depth 0 and
+    /// the elements of an array or object are one level deeper than it.
+    /// Parsing recurses once per level, so a very large maximum can
+    /// overflow the stack on deeply nested input.
+    pub max_depth: usize,
+    /// The maximum number of elements across all arrays and objects,
+    /// where each array item and each object member is an element.
+    pub max_elements: usize,
+}
+
+impl Default for Limits {
+    /// Returns limits with a maximum depth of 128, the default limit of
+    /// [`deserialize`](super::deserialize), and no maximum number of elements.
+    fn default() -> Self {
+        Self { max_depth: 128, max_elements: usize::MAX }
+    }
+}
+
+/// The `LimitError` enumeration represents an error that occurred
+/// while deserializing JSON that must stay within [`Limits`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LimitError {
+    /// A value is nested deeper than allowed.
+    TooDeep {
+        max_depth: usize,
+    },
+    /// The document has more elements than allowed.
+    TooManyElements {
+        max_elements: usize,
+    },
+    /// The JSON is invalid or does not match the target type.
+    Json {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooDeep { max_depth } => write!(f, "Value exceeds the maximum nesting depth of {}", max_depth),
+            Self::TooManyElements { max_elements } => write!(f, "Value exceeds the maximum of {} elements", max_elements),
+            Self::Json { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+/// Deserializes a JSON string into a value, failing as soon as the
+/// document exceeds `limits` and before any value is built, so that
+/// very deep or very wide documents cannot exhaust memory.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, Limits, LimitError, Value};
+/// let limits = Limits { max_elements: 3, ..Limits::default() };
+/// assert!(json::deserialize_with_limits::<Value>("[1, 2, 3]", limits).is_ok());
+/// assert_eq!(Err(LimitError::TooManyElements { max_elements: 3 }), json::deserialize_with_limits::<Value>("[1, 2, 3, 4]", limits));
+/// ```
+pub fn deserialize_with_limits<T>(string: &str, limits: Limits) -> Result<T, LimitError>
+    where T: DeserializeOwned
+{
+    // each open container is an object, with whether a key
+    // is expected next, or an array
+    let mut containers = Vec::<Option<bool>>::new();
+    let mut elements = 0usize;
+    for token in lexer::tokens(string) {
+        // a lexical error is reported by the parser below
+        let Ok((token, _)) = token else {
+            break;
+        };
+        let starts_element = match (&token, containers.last()) {
+            (Token::EndObject | Token::EndArray | Token::Colon | Token::Comma, _) => false,
+            (_, Some(None)) => true,
+            (Token::String(_), Some(Some(true))) => true,
+            _ => false,
+        };
+        if starts_element {
+            elements += 1;
+            if elements > limits.max_elements {
+                return Err(LimitError::TooManyElements { max_elements: limits.max_elements });
+            }
+            if containers.len() > limits.max_depth {
+                return Err(LimitError::TooDeep { max_depth: limits.max_depth });
+            }
+        }
+        match (token, containers.last_mut()) {
+            (Token::BeginObject, _) => containers.push(Some(true)),
+            (Token::BeginArray, _) => containers.push(None),
+            (Token::EndObject | Token::EndArray, _) => {
+                containers.pop();
+            },
+            (Token::Comma, Some(Some(expects_key))) => *expects_key = true,
+            (Token::String(_), Some(Some(expects_key))) => *expects_key = false,
+            _ => {},
+        }
+    }
+    // the depth was checked above, so the parser's own limit of 128
+    // would only reject documents that `limits` allows
+    let mut deserializer = serde_json::Deserializer::from_str(string);
+    deserializer.disable_recursion_limit();
+    let r = T::deserialize(&mut deserializer).and_then(|r| deserializer.end().map(|_| r));
+    r.map_err(|e| LimitError::Json { message: e.to_string() })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::{json, Value};
+
+    #[test]
+    fn element_limit() {
+        let limits = Limits { max_elements: 6, ..Limits::default() };
+        assert_eq!(Ok(json!([1, [2, 3], {"a": 4}])), deserialize_with_limits::<Value>(r#"[1, [2, 3], {"a": 4}]"#, limits));
+        assert_eq!(Ok(json!({"a": "b", "c": "d"})), deserialize_with_limits::<Value>(r#"{"a": "b", "c": "d"}"#, limits));
+        assert_eq!(Ok(json!(1)), deserialize_with_limits::<Value>("1", Limits { max_elements: 0, max_depth: 0 }));
+
+        let wide = format!("[{}]", vec!["0"; 1000].join(","));
+        assert_eq!(Err(LimitError::TooManyElements { max_elements: 6 }), deserialize_with_limits::<Value>(&wide, limits));
+        assert_eq!(Err(LimitError::TooManyElements { max_elements: 6 }), deserialize_with_limits::<Value>(r#"[[1, 2], [3, 4], [5]]"#, limits));
+        assert!(deserialize_with_limits::<Vec<u8>>(&wide, Limits::default()).is_ok());
+    }
+
+    #[test]
+    fn depth_limit() {
+        let limits = Limits { max_depth: 2, ..Limits::default() };
+        assert!(deserialize_with_limits::<Value>(r#"{"a": [1]}"#, limits).is_ok());
+        assert_eq!(Err(LimitError::TooDeep { max_depth: 2 }), deserialize_with_limits::<Value>(r#"{"a": [[1]]}"#, limits));
+        let flat = Limits { max_depth: 0, ..limits };
+        assert!(deserialize_with_limits::<Value>("[]", flat).is_ok());
+        assert_eq!(Err(LimitError::TooDeep { max_depth: 0 }), deserialize_with_limits::<Value>("[1]", flat));
+
+        let nested = "[".repeat(200) + &"]".repeat(200);
+        assert!(deserialize_with_limits::<Value>(&nested, Limits { max_depth: 300, ..limits }).is_ok());
+        assert_eq!(Err(LimitError::TooDeep { max_depth: 128 }), deserialize_with_limits::<Value>(&nested, Limits::default()));
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(matches!(deserialize_with_limits::<Value>("[1,", Limits::default()), Err(LimitError::Json { .. })));
+        assert!(matches!(deserialize_with_limits::<u8>(r#""x""#, Limits::default()), Err(LimitError::Json { .. })));
+        assert!(matches!(deserialize_with_limits::<Value>("[1] 2", Limits::default()), Err(LimitError::Json { .. })));
+    }
+}
@@ -0,0 +1,185 @@
+use super::{pointer, Map, Value};
+use std::collections::HashMap;
+
+/// The `LinesError` enumeration represents an error that occurred
+/// while reading a value from lines given to [`from_lines`]. Each
+/// variant carries the number of the line, starting at 1.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LinesError {
+    /// The line is not a JSON Pointer followed by `=` and a JSON value.
+    InvalidLine {
+        line: usize,
+    },
+    /// The line sets a value that another line already sets,
+    /// or that is inside a value that another line sets.
+    Conflict {
+        line: usize,
+    },
+}
+
+impl std::fmt::Display for LinesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLine { line } => write!(f, "Line {} is not a pointer and value pair", line),
+            Self::Conflict { line } => write!(f, "Line {} conflicts with a previous line", line),
+        }
+    }
+}
+
+impl std::error::Error for LinesError {}
+
+/// Converts a value into lines of the form `pointer=value`, one for each
+/// scalar, sorted by pointer, such as for storing JSON in version control
+/// with clean line-based diffs. Each pointer is a JSON Pointer and each
+/// value is compact JSON. Empty arrays and objects have their own lines.
+///
+/// The order of object keys does not affect the output.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(vec!["/a/0=true", "/a/1=null", "/b=\"x\""], json::to_lines(&json!({"b": "x", "a": [true, null]})));
+/// ```
+pub fn to_lines(value: &Value) -> Vec<String> {
+    let mut lines = Vec::<(String, String)>::new();
+    append_lines(&mut lines, value, "".to_owned());
+    lines.sort_by(|(a, _), (b, _)| a.cmp(b));
+    lines.into_iter().map(|(p, v)| p + "=" + &v).collect()
+}
+
+fn append_lines(output: &mut Vec<(String, String)>, value: &Value, at: String) {
+    match value {
+        Value::Object(o) if !o.is_empty() => {
+            for (k, v) in o {
+                append_lines(output, v, pointer::push_token(&at, k));
+            }
+        },
+        Value::Array(a) if !a.is_empty() => {
+            for (i, v) in a.iter().enumerate() {
+                append_lines(output, v, format!("{}/{}", at, i));
+            }
+        },
+        _ => output.push((at, value.to_string())),
+    }
+}
+
+/// A value under construction by [`from_lines`].
+enum Node {
+    Leaf(Value),
+    Branch(HashMap<String, Option<Node>>),
+}
+
+/// Reads a value back from lines produced by [`to_lines`]. The lines
+/// may be given in any order and empty lines are skipped.
+///
+/// A line is split at the first `=` that is followed by valid JSON, so
+/// keys may contain `=`. Since pointers do not distinguish array indices
+/// from object keys, a container whose keys are exactly `0`, `1`, and so
+/// on is read as an array.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(Ok(json!({"a": [1, {}]})), json::from_lines(["/a/1={}", "/a/0=1"]));
+/// ```
+pub fn from_lines<'a, I: IntoIterator<Item = &'a str>>(lines: I) -> Result<Value, LinesError> {
+    let mut root: Option<Node> = None;
+    for (i, line) in lines.into_iter().enumerate() {
+        let number = i + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (tokens, value) = line.match_indices('=').find_map(|(j, _)| {
+            let value = serde_json::from_str::<Value>(&line[j + 1..]).ok()?;
+            Some((pointer::parse(&line[..j])?, value))
+        }).ok_or(LinesError::InvalidLine { line: number })?;
+
+        if !insert(&mut root, &tokens, value) {
+            return Err(LinesError::Conflict { line: number });
+        }
+    }
+    Ok(root.map_or(Value::Null, into_value))
+}
+
+/// Inserts a value at the given path, returning `false` on a conflict.
+fn insert(slot: &mut Option<Node>, tokens: &[String], value: Value) -> bool {
+    let Some((first, rest)) = tokens.split_first() else {
+        if slot.is_some() {
+            return false;
+        }
+        *slot = Some(Node::Leaf(value));
+        return true;
+    };
+    let Node::Branch(children) = slot.get_or_insert_with(|| Node::Branch(HashMap::new())) else {
+        return false;
+    };
+    insert(children.entry(first.clone()).or_insert(None), rest, value)
+}
+
+fn into_value(node: Node) -> Value {
+    match node {
+        Node::Leaf(value) => value,
+        Node::Branch(children) => {
+            let is_array = (0..children.len()).all(|i| children.contains_key(&i.to_string()));
+            if is_array {
+                let mut children: Vec<(usize, Option<Node>)> = children.into_iter().map(|(k, v)| (k.parse().unwrap(), v)).collect();
+                children.sort_by_key(|(i, _)| *i);
+                Value::Array(children.into_iter().map(|(_, v)| v.map_or(Value::Null, into_value)).collect())
+            } else {
+                Value::Object(children.into_iter().map(|(k, v)| (k, v.map_or(Value::Null, into_value))).collect::<Map<_, _>>())
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn lines() {
+        let value = json!({"b": {"c": 1.5, "d": [true, null, "x=y"]}, "a": {}, "e": [], "a/~k=": "v"});
+        assert_eq!(vec![
+            "/a={}",
+            "/a~1~0k==\"v\"",
+            "/b/c=1.5",
+            "/b/d/0=true",
+            "/b/d/1=null",
+            "/b/d/2=\"x=y\"",
+            "/e=[]",
+        ], to_lines(&value));
+
+        let reordered = json!({"e": [], "a/~k=": "v", "a": {}, "b": {"d": [true, null, "x=y"], "c": 1.5}});
+        assert_eq!(to_lines(&value), to_lines(&reordered));
+        assert_eq!(vec!["=1"], to_lines(&json!(1)));
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let values = [
+            json!({"b": {"c": 1.5, "d": [true, null, "x=y"]}, "a": {}, "e": [], "a/~k=": "v"}),
+            json!([[1, 2], [], {"k": [{"z": "="}]}]),
+            json!((0..12).collect::<Vec<_>>()),
+            json!("root"),
+            json!({}),
+        ];
+        for value in values {
+            let lines = to_lines(&value);
+            assert_eq!(Ok(value), from_lines(lines.iter().map(|l| l.as_str())));
+        }
+        assert_eq!(Ok(json!({"a": 1, "b": 2})), from_lines(["/b=2", "", "/a=1"]));
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert_eq!(Err(LinesError::InvalidLine { line: 2 }), from_lines(["/a=1", "/b"]));
+        assert_eq!(Err(LinesError::InvalidLine { line: 1 }), from_lines(["a=1"]));
+        assert_eq!(Err(LinesError::InvalidLine { line: 1 }), from_lines(["/a=x"]));
+        assert_eq!(Err(LinesError::Conflict { line: 2 }), from_lines(["/a=1", "/a/b=2"]));
+        assert_eq!(Err(LinesError::Conflict { line: 2 }), from_lines(["/a/b=2", "/a=1"]));
+        assert_eq!(Err(LinesError::Conflict { line: 3 }), from_lines(["/x=1", "/a=1", "/a=1"]));
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::{BufRead, Write};
+use super::{Error, Result, Value};
+
+/// Reads NDJSON (newline-delimited JSON) from `reader`, passes each record
+/// through `f` and writes the returned records as NDJSON to `writer`,
+/// holding a single record in memory at a time. Returning `None` from `f`
+/// drops the record. Blank lines are skipped.
+///
+/// A line that is not valid JSON is skipped without aborting, and returned
+/// along with its 1-based line number. I/O errors abort the transformation.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut output = Vec::<u8>::new();
+/// let input = "{\"n\":1}\n{\"n\":2}\n";
+/// json::transform_ndjson(input.as_bytes(), &mut output, |mut v| {
+///     v["n"] = json!(v["n"].as_i64()? * 10);
+///     Some(v)
+/// }).unwrap();
+/// assert_eq!("{\"n\":10}\n{\"n\":20}\n", String::from_utf8(output).unwrap());
+/// ```
+pub fn transform_ndjson<R, W, F>(mut reader: R, mut writer: W, mut f: F) -> Result<Vec<(usize, Error)>>
+    where
+        R: BufRead,
+        W: Write,
+        F: FnMut(Value) -> Option<Value>
+{
+    let mut line_errors = Vec::<(usize, Error)>::new();
+    let mut line = String::new();
+    let mut number = 0usize;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(Error::io)? == 0 {
+            break;
+        }
+        number += 1;
+        let record = line.trim_end_matches(['\n', '\r']);
+        if record.trim().is_empty() {
+            continue;
+        }
+        let value = match serde_json::from_str::<Value>(record) {
+            Ok(value) => value,
+            Err(error) => {
+                line_errors.push((number, error));
+                continue;
+            },
+        };
+        if let Some(value) = f(value) {
+            serde_json::to_writer(&mut writer, &value)?;
+            writer.write_all(b"\n").map_err(Error::io)?;
+        }
+    }
+    writer.flush().map_err(Error::io)?;
+    Ok(line_errors)
+}
+
+/// Reads NDJSON from `reader` and writes to `writer` the lines whose record
+/// has a value equal to `equals` at the JSON Pointer `pointer`, such as for
+/// keeping the log records of a certain level. Matching lines are written
+/// as they are, followed by a newline. Returns the number of lines kept.
+///
+/// Blank lines and lines that are not valid JSON are skipped; use
+/// [`filter_ndjson_with_skips`] to also count the invalid lines.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut output = Vec::<u8>::new();
+/// let input = "{\"level\":\"error\"}\n{\"level\":\"info\"}\n";
+/// assert_eq!(1, json::filter_ndjson(input.as_bytes(), &mut output, "/level", &json!("error")).unwrap());
+/// assert_eq!("{\"level\":\"error\"}\n", String::from_utf8(output).unwrap());
+/// ```
+pub fn filter_ndjson<R, W>(reader: R, writer: W, pointer: &str, equals: &Value) -> Result<usize>
+    where
+        R: BufRead,
+        W: Write
+{
+    filter_ndjson_with_skips(reader, writer, pointer, equals).map(|(kept, _)| kept)
+}
+
+/// Filters NDJSON as [`filter_ndjson`] does, returning both the number of
+/// lines kept and the number of lines skipped because they are not valid
+/// JSON. I/O errors abort the filtering.
+pub fn filter_ndjson_with_skips<R, W>(mut reader: R, mut writer: W, pointer: &str, equals: &Value) -> Result<(usize, usize)>
+    where
+        R: BufRead,
+        W: Write
+{
+    let (mut kept, mut skipped) = (0usize, 0usize);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(Error::io)? == 0 {
+            break;
+        }
+        let record = line.trim_end_matches(['\n', '\r']);
+        if record.trim().is_empty() {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(record) else {
+            skipped += 1;
+            continue;
+        };
+        if value.pointer(pointer) == Some(equals) {
+            writer.write_all(record.as_bytes()).map_err(Error::io)?;
+            writer.write_all(b"\n").map_err(Error::io)?;
+            kept += 1;
+        }
+    }
+    writer.flush().map_err(Error::io)?;
+    Ok((kept, skipped))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn transform() {
+        let input = "{\"id\":1,\"keep\":true}\r\n{\"id\":2,\"keep\":false}\n\n{broken\n{\"id\":3,\"keep\":true}";
+        let mut output = Vec::<u8>::new();
+        let errors = transform_ndjson(input.as_bytes(), &mut output, |v| {
+            if v["keep"] == json!(true) { Some(json!({"id": v["id"]})) } else { None }
+        }).unwrap();
+        assert_eq!("{\"id\":1}\n{\"id\":3}\n", String::from_utf8(output).unwrap());
+        assert_eq!(vec![4], errors.iter().map(|(line, _)| *line).collect::<Vec<_>>());
+        assert!(errors[0].1.is_syntax());
+
+        let mut output = Vec::<u8>::new();
+        assert!(transform_ndjson("".as_bytes(), &mut output, Some).unwrap().is_empty());
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn filter() {
+        let input = concat!(
+            "{\"ctx\": {\"level\": \"error\"}, \"id\": 1}\n",
+            "{\"ctx\":{\"level\":\"info\"},\"id\":2}\r\n",
+            "not json\n",
+            "\n",
+            "{\"ctx\":{\"level\":\"error\",\"extra\":[1]},\"id\":3}\n",
+            "{\"id\":4}\n",
+            "[\"error\"]",
+        );
+        let mut output = Vec::<u8>::new();
+        assert_eq!(2, filter_ndjson(input.as_bytes(), &mut output, "/ctx/level", &json!("error")).unwrap());
+        assert_eq!(
+            "{\"ctx\": {\"level\": \"error\"}, \"id\": 1}\n{\"ctx\":{\"level\":\"error\",\"extra\":[1]},\"id\":3}\n",
+            String::from_utf8(output).unwrap(),
+        );
+
+        let mut output = Vec::<u8>::new();
+        assert_eq!((1, 1), filter_ndjson_with_skips(input.as_bytes(), &mut output, "/0", &json!("error")).unwrap());
+        assert_eq!("[\"error\"]\n", String::from_utf8(output).unwrap());
+
+        let mut output = Vec::<u8>::new();
+        assert_eq!((0, 1), filter_ndjson_with_skips(input.as_bytes(), &mut output, "/id", &json!("1")).unwrap());
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use super::*;
+use serde::ser::{self, Error as _};
+
+/// Indicates how non-finite floating point numbers, which JSON
+/// cannot represent, are serialized.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum NonFinitePolicy {
+    /// Fails the serialization.
+    #[default]
+    Error,
+    /// Serializes non-finite numbers as `null`.
+    Null,
+    /// Serializes non-finite numbers as the strings `"NaN"`,
+    /// `"Infinity"` and `"-Infinity"`.
+    String,
+}
+
+/// Serializes a value into a JSON string, handling `NaN` and
+/// infinite floating point numbers according to `policy`.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, NonFinitePolicy};
+/// assert_eq!(r#"[1.0,"NaN"]"#, json::serialize_with_nonfinite(&[1.0, f64::NAN], NonFinitePolicy::String).unwrap());
+/// assert!(json::serialize_with_nonfinite(&f64::INFINITY, NonFinitePolicy::Error).is_err());
+/// ```
+pub fn serialize_with_nonfinite<T>(value: &T, policy: NonFinitePolicy) -> Result<String>
+    where T: ?Sized + Serialize
+{
+    serde_json::to_string(&Wrapped(value, policy))
+}
+
+struct Wrapped<'a, T: ?Sized>(&'a T, NonFinitePolicy);
+
+impl<'a, T: ?Sized + Serialize> Serialize for Wrapped<'a, T> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize(NonFiniteSerializer { inner: serializer, policy: self.1 })
+    }
+}
+
+struct NonFiniteSerializer<S> {
+    inner: S,
+    policy: NonFinitePolicy,
+}
+
+struct Compound<C> {
+    inner: C,
+    policy: NonFinitePolicy,
+}
+
+impl<S: ser::Serializer> NonFiniteSerializer<S> {
+    fn serialize_non_finite(self, value: f64) -> std::result::Result<S::Ok, S::Error> {
+        match self.policy {
+            NonFinitePolicy::Error => Err(S::Error::custom(format!("cannot serialize non-finite number {}", value))),
+            NonFinitePolicy::Null => self.inner.serialize_unit(),
+            NonFinitePolicy::String => self.inner.serialize_str(
+                if value.is_nan() { "NaN" } else if value > 0.0 { "Infinity" } else { "-Infinity" }
+            ),
+        }
+    }
+}
+
+macro_rules! forward {
+    ($($method:ident($($ty:ty)?);)*) => {
+        $(
+            fn $method(self $(, v: $ty)?) -> std::result::Result<S::Ok, S::Error> {
+                self.inner.$method($(v as $ty)?)
+            }
+        )*
+    };
+}
+
+impl<S: ser::Serializer> ser::Serializer for NonFiniteSerializer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Compound<S::SerializeSeq>;
+    type SerializeTuple = Compound<S::SerializeTuple>;
+    type SerializeTupleStruct = Compound<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Compound<S::SerializeTupleVariant>;
+    type SerializeMap = Compound<S::SerializeMap>;
+    type SerializeStruct = Compound<S::SerializeStruct>;
+    type SerializeStructVariant = Compound<S::SerializeStructVariant>;
+
+    forward! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+    }
+
+    fn serialize_f32(self, v: f32) -> std::result::Result<S::Ok, S::Error> {
+        if v.is_finite() { self.inner.serialize_f32(v) } else { self.serialize_non_finite(v as f64) }
+    }
+
+    fn serialize_f64(self, v: f64) -> std::result::Result<S::Ok, S::Error> {
+        if v.is_finite() { self.inner.serialize_f64(v) } else { self.serialize_non_finite(v) }
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_some(&Wrapped(value, self.policy))
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(self, name: &'static str, index: u32, variant: &'static str) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_variant(name, index, variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, name: &'static str, value: &T) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_struct(name, &Wrapped(value, self.policy))
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, name: &'static str, index: u32, variant: &'static str, value: &T) -> std::result::Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_variant(name, index, variant, &Wrapped(value, self.policy))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> std::result::Result<Self::SerializeSeq, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_seq(len)?, policy })
+    }
+
+    fn serialize_tuple(self, len: usize) -> std::result::Result<Self::SerializeTuple, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_tuple(len)?, policy })
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, len: usize) -> std::result::Result<Self::SerializeTupleStruct, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_tuple_struct(name, len)?, policy })
+    }
+
+    fn serialize_tuple_variant(self, name: &'static str, index: u32, variant: &'static str, len: usize) -> std::result::Result<Self::SerializeTupleVariant, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_tuple_variant(name, index, variant, len)?, policy })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> std::result::Result<Self::SerializeMap, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_map(len)?, policy })
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> std::result::Result<Self::SerializeStruct, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_struct(name, len)?, policy })
+    }
+
+    fn serialize_struct_variant(self, name: &'static str, index: u32, variant: &'static str, len: usize) -> std::result::Result<Self::SerializeStructVariant, S::Error> {
+        let policy = self.policy;
+        Ok(Compound { inner: self.inner.serialize_struct_variant(name, index, variant, len)?, policy })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<C: ser::SerializeSeq> ser::SerializeSeq for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_element(&Wrapped(value, self.policy))
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTuple> ser::SerializeTuple for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_element(&Wrapped(value, self.policy))
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTupleStruct> ser::SerializeTupleStruct for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_field(&Wrapped(value, self.policy))
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeTupleVariant> ser::SerializeTupleVariant for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_field(&Wrapped(value, self.policy))
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeMap> ser::SerializeMap for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_key(&Wrapped(key, self.policy))
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_value(&Wrapped(value, self.policy))
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeStruct> ser::SerializeStruct for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_field(key, &Wrapped(value, self.policy))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> std::result::Result<(), C::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: ser::SerializeStructVariant> ser::SerializeStructVariant for Compound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> std::result::Result<(), C::Error> {
+        self.inner.serialize_field(key, &Wrapped(value, self.policy))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> std::result::Result<(), C::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> std::result::Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Sample {
+        x: f64,
+        y: Vec<f32>,
+        z: Option<f64>,
+    }
+
+    #[test]
+    fn policies() {
+        let sample = Sample { x: f64::NAN, y: vec![1.5, f32::INFINITY], z: Some(f64::NEG_INFINITY) };
+        assert!(serialize_with_nonfinite(&sample, NonFinitePolicy::Error).is_err());
+        assert_eq!(r#"{"x":null,"y":[1.5,null],"z":null}"#, serialize_with_nonfinite(&sample, NonFinitePolicy::Null).unwrap());
+        assert_eq!(r#"{"x":"NaN","y":[1.5,"Infinity"],"z":"-Infinity"}"#, serialize_with_nonfinite(&sample, NonFinitePolicy::String).unwrap());
+
+        let finite = Sample { x: 1.0, y: vec![], z: None };
+        assert_eq!(r#"{"x":1.0,"y":[],"z":null}"#, serialize_with_nonfinite(&finite, NonFinitePolicy::Error).unwrap());
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers for building JSON Pointers ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)).
+
+/// Escapes a single reference token, replacing `~` by `~0` and `/` by `~1`.
+pub(crate) fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Unescapes a single reference token, replacing `~1` by `/` and `~0` by `~`.
+pub(crate) fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+/// Returns `None` if the pointer is neither empty nor starts with `/`.
+pub(crate) fn parse(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(vec![]);
+    }
+    Some(pointer.strip_prefix('/')?.split('/').map(unescape_token).collect())
+}
+
+/// Appends a reference token to a JSON Pointer.
+pub(crate) fn push_token(pointer: &str, token: &str) -> String {
+    format!("{}/{}", pointer, escape_token(token))
+}
@@ -0,0 +1,74 @@
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use super::{Error, Result};
+use super::super::generic_deserialization::DeserializeOwned;
+
+/// Reader that reports the number of bytes read so far.
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    total: u64,
+    on_progress: F,
+}
+
+impl<R: Read, F: FnMut(u64, u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n != 0 {
+            self.read += n as u64;
+            (self.on_progress)(self.read, self.total);
+        }
+        Ok(n)
+    }
+}
+
+/// Deserializes JSON from a seekable reader into a value, reporting progress.
+///
+/// `on_progress` is called with the number of bytes read so far
+/// and the total number of bytes, which is measured by seeking
+/// from the current position to the end of the reader.
+pub fn deserialize_from_reader_progress<R, T, F>(mut reader: R, on_progress: F) -> Result<T>
+    where
+        R: Read + Seek,
+        T: DeserializeOwned,
+        F: FnMut(u64, u64)
+{
+    let start = reader.stream_position().map_err(Error::io)?;
+    let end = reader.seek(SeekFrom::End(0)).map_err(Error::io)?;
+    reader.seek(SeekFrom::Start(start)).map_err(Error::io)?;
+    deserialize_from_reader_progress_sized(reader, end - start, on_progress)
+}
+
+/// Deserializes JSON from a reader into a value, reporting progress
+/// against an explicitly given `total` number of bytes.
+///
+/// This is the variant of [`deserialize_from_reader_progress`] for
+/// readers that cannot seek.
+pub fn deserialize_from_reader_progress_sized<R, T, F>(reader: R, total: u64, on_progress: F) -> Result<T>
+    where
+        R: Read,
+        T: DeserializeOwned,
+        F: FnMut(u64, u64)
+{
+    serde_json::from_reader(BufReader::new(ProgressReader { inner: reader, read: 0, total, on_progress }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::Value;
+
+    #[test]
+    fn progress() {
+        let document = format!("[{}0]", "0,".repeat(20_000));
+        let mut reports = Vec::<(u64, u64)>::new();
+        let value: Value = deserialize_from_reader_progress(std::io::Cursor::new(document.as_bytes()), |read, total| reports.push((read, total))).unwrap();
+        assert_eq!(20_001, value.as_array().unwrap().len());
+        assert!(reports.len() > 1);
+        assert!(reports.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!((document.len() as u64, document.len() as u64), *reports.last().unwrap());
+
+        let mut last = 0;
+        let _: Value = deserialize_from_reader_progress_sized(br#"{"a": 1}"#.as_slice(), 8, |read, _| last = read).unwrap();
+        assert_eq!(8, last);
+    }
+}
@@ -0,0 +1,185 @@
+/*!
+Convert between JSON objects and query strings.
+
+Arrays are represented by repeating a key, as in `b=x&b=y`,
+and nested objects are represented using bracket notation,
+as in `a[b]=1`. Keys and values are percent-encoded.
+
+# Example
+
+```
+use rialight_util::serialization::{json, json::json};
+
+let value = json!({"a": 1, "b": ["x", "y"]});
+assert_eq!("a=1&b=x&b=y", json::query_string::to_query_string(&value).unwrap());
+assert_eq!(value, json::query_string::from_query_string("a=1&b=x&b=y"));
+```
+*/
+
+use super::{Map, Value};
+use super::key_path::infer_value;
+use crate::uri::{decode_uri_component, encode_uri_component};
+
+/// The `QueryError` enumeration represents an error
+/// that occurred while converting a value into a query string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum QueryError {
+    /// The value to convert is not an object.
+    NotAnObject,
+    /// A nested array appears at the given key, which
+    /// cannot be represented by a query string.
+    NestedArray {
+        key: String,
+    },
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnObject => write!(f, "Only objects can be converted into query strings"),
+            Self::NestedArray { key } => write!(f, "Array at {:?} contains a non-scalar element", key),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Converts a JSON object into a query string.
+///
+/// Scalars are written as their textual representation and
+/// `null` is written as an empty value. Arrays may only contain
+/// scalars.
+pub fn to_query_string(value: &Value) -> Result<String, QueryError> {
+    let Value::Object(map) = value else {
+        return Err(QueryError::NotAnObject);
+    };
+    let mut pairs = Vec::<String>::new();
+    write_object(map, None, &mut pairs)?;
+    Ok(pairs.join("&"))
+}
+
+fn write_object(map: &Map<String, Value>, parent_key: Option<&str>, pairs: &mut Vec<String>) -> Result<(), QueryError> {
+    for (k, v) in map {
+        let key = match parent_key {
+            Some(p) => format!("{}[{}]", p, encode_uri_component(k)),
+            None => encode_uri_component(k),
+        };
+        match v {
+            Value::Object(map) => write_object(map, Some(&key), pairs)?,
+            Value::Array(elements) => {
+                for element in elements {
+                    if element.is_array() || element.is_object() {
+                        return Err(QueryError::NestedArray { key: decode_uri_component(&key) });
+                    }
+                    pairs.push(format!("{}={}", key, encode_uri_component(&scalar_to_string(element))));
+                }
+            },
+            v => pairs.push(format!("{}={}", key, encode_uri_component(&scalar_to_string(v)))),
+        }
+    }
+    Ok(())
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => "".to_owned(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+/// Converts a query string into a JSON object.
+///
+/// Repeated keys produce arrays and bracket notation produces nested
+/// objects. A key ending in empty brackets, as in `a[]=1&a[]=2`, always
+/// produces an array. The type of each value is inferred: `true` and
+/// `false` become booleans, numeric strings become numbers and anything
+/// else becomes a string. A `+` is decoded as a space.
+///
+/// A pair that conflicts with an earlier one, such as `a[b]=2` after
+/// `a=1`, is ignored, keeping the earlier value.
+pub fn from_query_string(string: &str) -> Value {
+    let mut r = Map::new();
+    'pairs: for pair in string.trim_start_matches('?').split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = infer_value(&decode(value));
+        let mut segments = parse_key(key);
+        let append = segments.len() > 1 && segments.last().is_some_and(|s| s.is_empty());
+        if append {
+            segments.pop();
+        }
+        let mut target = &mut r;
+        for segment in &segments[..segments.len() - 1] {
+            let entry = target.entry(segment.clone()).or_insert_with(|| Value::Object(Map::new()));
+            let Some(object) = entry.as_object_mut() else {
+                continue 'pairs;
+            };
+            target = object;
+        }
+        let last = segments.last().unwrap().clone();
+        match target.get_mut(&last) {
+            Some(Value::Array(elements)) => elements.push(value),
+            Some(Value::Object(_)) => {},
+            Some(existing) => {
+                let previous = existing.take();
+                *existing = Value::Array(vec![previous, value]);
+            },
+            None => {
+                target.insert(last, if append { Value::Array(vec![value]) } else { value });
+            },
+        }
+    }
+    Value::Object(r)
+}
+
+/// Splits a key such as `a[b][c]` into its decoded segments.
+fn parse_key(key: &str) -> Vec<String> {
+    let (first, mut rest) = key.split_once('[').unwrap_or((key, ""));
+    let mut segments = vec![decode(first)];
+    while let Some((segment, after)) = rest.split_once(']') {
+        segments.push(decode(segment));
+        rest = after.strip_prefix('[').unwrap_or("");
+    }
+    segments
+}
+
+fn decode(string: &str) -> String {
+    decode_uri_component(&string.replace('+', " "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn round_trip() {
+        let value = json!({"a": 1, "b": ["x", "y"]});
+        assert_eq!("a=1&b=x&b=y", to_query_string(&value).unwrap());
+        assert_eq!(value, from_query_string(&to_query_string(&value).unwrap()));
+
+        let value = json!({"a": {"b": 1, "c": {"d": true}}, "e f": "g&h=i"});
+        let string = to_query_string(&value).unwrap();
+        assert_eq!("a[b]=1&a[c][d]=true&e%20f=g%26h%3Di", string);
+        assert_eq!(value, from_query_string(&string));
+
+        assert_eq!(json!({"q": "a b"}), from_query_string("?q=a+b"));
+        assert_eq!(Err(QueryError::NotAnObject), to_query_string(&json!([1])));
+        assert_eq!(Err(QueryError::NestedArray { key: "a".into() }), to_query_string(&json!({"a": [[1]]})));
+    }
+
+    #[test]
+    fn empty_brackets() {
+        assert_eq!(json!({"a": [1, 2]}), from_query_string("a[]=1&a[]=2"));
+        assert_eq!(json!({"a": [1]}), from_query_string("a[]=1"));
+        assert_eq!(json!({"a": {"b": ["x", "y"]}}), from_query_string("a[b][]=x&a[b][]=y"));
+        assert_eq!(json!({"a": [1, 2, 3]}), from_query_string("a=1&a[]=2&a=3"));
+    }
+
+    #[test]
+    fn conflicts() {
+        assert_eq!(json!({"a": 1}), from_query_string("a=1&a[b]=2"));
+        assert_eq!(json!({"a": {"b": 2}}), from_query_string("a[b]=2&a=1"));
+        assert_eq!(json!({"a": [1, 2], "c": 3}), from_query_string("a=1&a=2&a[b]=x&c=3"));
+    }
+}
@@ -0,0 +1,171 @@
+use super::{Map, Value};
+use crate::uri::decode_uri_component;
+
+/// The `RefError` enumeration represents an error that
+/// occurred while resolving `$ref` references.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RefError {
+    /// A reference points to a missing node.
+    NotFound {
+        reference: String,
+    },
+    /// Resolving a reference requires resolving itself.
+    Cycle {
+        reference: String,
+    },
+    /// The resolved value has more nodes than allowed, such as when
+    /// references to references expand exponentially.
+    TooLarge {
+        max_nodes: usize,
+    },
+}
+
+impl std::fmt::Display for RefError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound { reference } => write!(f, "Reference {:?} points to a missing node", reference),
+            Self::Cycle { reference } => write!(f, "Reference {:?} is cyclic", reference),
+            Self::TooLarge { max_nodes } => write!(f, "Resolved value exceeds the maximum of {} nodes", max_nodes),
+        }
+    }
+}
+
+impl std::error::Error for RefError {}
+
+/// Replaces every `{"$ref": "#/pointer"}` object in a value by a copy of
+/// the subtree at that JSON Pointer in the same value, as in bundled JSON
+/// Schemas and OpenAPI documents. Referenced subtrees are resolved too.
+/// Other members of an object containing a local `$ref` are discarded.
+///
+/// Only local references, which start with `#/`, are resolved; others
+/// are left as they are. A reference is a URI fragment, so it is
+/// percent-decoded before being used as a JSON Pointer. Fails if a
+/// reference points to a missing node, if a reference cycle is found
+/// or if the resolved value has more than 1,000,000 nodes.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let value = json!({"defs": {"id": {"type": "integer"}}, "id": {"$ref": "#/defs/id"}});
+/// assert_eq!(json!({"type": "integer"}), json::resolve_refs(&value).unwrap()["id"]);
+/// ```
+pub fn resolve_refs(value: &Value) -> Result<Value, RefError> {
+    resolve_refs_bounded(value, 1_000_000)
+}
+
+/// Resolves references as [`resolve_refs`] does, failing if the resolved
+/// value has more than `max_nodes` nodes, counting every array, object
+/// and scalar.
+pub fn resolve_refs_bounded(value: &Value, max_nodes: usize) -> Result<Value, RefError> {
+    Resolver { root: value, active: vec![], nodes: 0, max_nodes }.resolve_node(value)
+}
+
+fn local_ref(value: &Value) -> Option<&str> {
+    value.as_object()?.get("$ref")?.as_str().filter(|r| r.starts_with("#/"))
+}
+
+struct Resolver<'a> {
+    root: &'a Value,
+    /// The decoded pointers of the references being resolved.
+    active: Vec<String>,
+    /// The number of nodes resolved so far.
+    nodes: usize,
+    max_nodes: usize,
+}
+
+impl<'a> Resolver<'a> {
+    fn resolve_node(&mut self, value: &Value) -> Result<Value, RefError> {
+        if let Some(reference) = local_ref(value) {
+            let pointer = decode_uri_component(&reference[1..]);
+            if self.active.contains(&pointer) {
+                return Err(RefError::Cycle { reference: reference.to_owned() });
+            }
+            let target = self.root.pointer(&pointer).ok_or_else(|| RefError::NotFound { reference: reference.to_owned() })?;
+            self.active.push(pointer);
+            let r = self.resolve_node(target);
+            self.active.pop();
+            return r;
+        }
+        self.nodes += 1;
+        if self.nodes > self.max_nodes {
+            return Err(RefError::TooLarge { max_nodes: self.max_nodes });
+        }
+        Ok(match value {
+            Value::Array(elements) => Value::Array(elements.iter().map(|e| self.resolve_node(e)).collect::<Result<_, _>>()?),
+            Value::Object(fields) => {
+                let mut r = Map::new();
+                for (k, v) in fields {
+                    r.insert(k.clone(), self.resolve_node(v)?);
+                }
+                Value::Object(r)
+            },
+            _ => value.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn simple_refs() {
+        let value = json!({
+            "definitions": {
+                "name": {"type": "string"},
+                "a/b": 1,
+                "person": {"type": "object", "properties": {"name": {"$ref": "#/definitions/name"}}},
+            },
+            "items": [{"$ref": "#/definitions/person", "description": "dropped"}],
+            "remote": {"$ref": "https://example.com/schema.json"},
+            "escaped": {"$ref": "#/definitions/a~1b"},
+        });
+        let resolved = resolve_refs(&value).unwrap();
+        assert_eq!(json!({"type": "object", "properties": {"name": {"type": "string"}}}), resolved["items"][0]);
+        assert_eq!(json!({"$ref": "https://example.com/schema.json"}), resolved["remote"]);
+        assert_eq!(json!(1), resolved["escaped"]);
+        assert_eq!(json!({"a": {"b": 1}}), resolve_refs(&json!({"a": {"b": 1}})).unwrap());
+        assert_eq!(Err(RefError::NotFound { reference: "#/missing".into() }), resolve_refs(&json!([{"$ref": "#/missing"}])));
+    }
+
+    #[test]
+    fn cyclic_refs() {
+        let value = json!({"a": {"$ref": "#/b"}, "b": {"$ref": "#/a"}});
+        assert!(matches!(resolve_refs(&value), Err(RefError::Cycle { .. })));
+        let value = json!({"node": {"children": [{"$ref": "#/node"}]}});
+        assert_eq!(Err(RefError::Cycle { reference: "#/node".into() }), resolve_refs(&value));
+        let value = json!({"a": {"$ref": "#/c"}, "b": {"$ref": "#/c"}, "c": 3});
+        assert_eq!(json!({"a": 3, "b": 3, "c": 3}), resolve_refs(&value).unwrap());
+    }
+
+    #[test]
+    fn percent_encoded_refs() {
+        let value = json!({"defs": {"a b": 1, "50%": 2}, "x": {"$ref": "#/defs/a%20b"}, "y": {"$ref": "#/defs/50%25"}});
+        let resolved = resolve_refs(&value).unwrap();
+        assert_eq!(json!(1), resolved["x"]);
+        assert_eq!(json!(2), resolved["y"]);
+        let value = json!({"a b": {"$ref": "#/a%20b"}});
+        assert_eq!(Err(RefError::Cycle { reference: "#/a%20b".into() }), resolve_refs(&value));
+    }
+
+    #[test]
+    fn expansion_bound() {
+        // each definition references the next one twice, doubling the size
+        let mut defs = Map::new();
+        for i in 0..40 {
+            let next = json!({"$ref": format!("#/d{}", i + 1)});
+            defs.insert(format!("d{}", i), json!([next, next]));
+        }
+        defs.insert("d40".into(), json!(0));
+        let value = Value::Object(defs);
+        assert_eq!(Err(RefError::TooLarge { max_nodes: 1_000_000 }), resolve_refs(&value));
+        assert_eq!(Err(RefError::TooLarge { max_nodes: 100 }), resolve_refs_bounded(&value, 100));
+
+        // the root, `a`, two copies of `b` with their elements, and `b`
+        let value = json!({"a": [{"$ref": "#/b"}, {"$ref": "#/b"}], "b": [1]});
+        assert_eq!(Ok(json!({"a": [[1], [1]], "b": [1]})), resolve_refs_bounded(&value, 8));
+        assert_eq!(Err(RefError::TooLarge { max_nodes: 7 }), resolve_refs_bounded(&value, 7));
+    }
+}
@@ -0,0 +1,249 @@
+use super::Value;
+
+/// A repair applied by [`repair_reporting`]. Each variant carries the
+/// byte offset in the input at which the repair applies.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Repair {
+    /// An unquoted object key, such as `{a: 1}`, was quoted.
+    UnquotedKey {
+        offset: usize,
+    },
+    /// A single-quoted string, such as `'a'`, was double-quoted.
+    SingleQuotes {
+        offset: usize,
+    },
+    /// A trailing comma, such as in `[1,]`, was removed.
+    TrailingComma {
+        offset: usize,
+    },
+    /// A missing `]` or `}` was added at the end of the input.
+    MissingClose {
+        offset: usize,
+    },
+}
+
+/// The `RepairError` enumeration represents an error that occurred
+/// because near-JSON text could not be repaired.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RepairError {
+    /// A string is not terminated at the end of the input.
+    UnterminatedString {
+        offset: usize,
+    },
+    /// The input ends right after an object key, so the missing
+    /// value is unknown.
+    MissingValue {
+        offset: usize,
+    },
+    /// The repaired text is still not valid JSON.
+    Json {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for RepairError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnterminatedString { offset } => write!(f, "String starting at offset {} is not terminated", offset),
+            Self::MissingValue { offset } => write!(f, "Value is missing at offset {}", offset),
+            Self::Json { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for RepairError {}
+
+/// Parses near-JSON text, repairing common mistakes, such as for a
+/// forgiving import of hand-written files. See [`repair_reporting`]
+/// for the repairs that are applied.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(Ok(json!({"a": ["x", 1]})), json::repair("{a: ['x', 1,]"));
+/// ```
+pub fn repair(input: &str) -> Result<Value, RepairError> {
+    repair_reporting(input).map(|(value, _)| value)
+}
+
+/// Parses near-JSON text as [`repair`] does, additionally returning
+/// each repair applied, in input order.
+///
+/// The repairs are:
+///
+/// - Object keys that are identifiers, such as `{a: 1}`, are quoted.
+/// - Single-quoted strings are converted to double-quoted strings.
+/// - Commas before a `]` or `}`, or at the end of the input, are removed.
+/// - Arrays and objects left open at the end of the input are closed.
+///
+/// Repairing is best-effort and lossy: the original text cannot be
+/// recovered from the result. Text that remains invalid after the
+/// repairs, such as one with unknown words like `undefined`, fails.
+pub fn repair_reporting(input: &str) -> Result<(Value, Vec<Repair>), RepairError> {
+    let mut repairs = Vec::<Repair>::new();
+    let mut r = String::with_capacity(input.len());
+    // each open container is an object, with whether a
+    // key is expected next, or an array
+    let mut containers = Vec::<Option<bool>>::new();
+    // whether the last token is an object key,
+    // possibly followed by a colon
+    let mut after_key = false;
+    let mut chars = input.char_indices().peekable();
+    while let Some((offset, ch)) = chars.next() {
+        if !(ch == ':' || ch.is_whitespace()) {
+            after_key = false;
+        }
+        match ch {
+            '"' | '\'' => {
+                if ch == '\'' {
+                    repairs.push(Repair::SingleQuotes { offset });
+                }
+                r.push('"');
+                let mut terminated = false;
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            let Some((_, escaped)) = chars.next() else {
+                                break;
+                            };
+                            if escaped != '\'' {
+                                r.push('\\');
+                            }
+                            r.push(escaped);
+                        },
+                        _ if c == ch => {
+                            terminated = true;
+                            break;
+                        },
+                        '"' => r.push_str("\\\""),
+                        _ => r.push(c),
+                    }
+                }
+                if !terminated {
+                    return Err(RepairError::UnterminatedString { offset });
+                }
+                r.push('"');
+                if let Some(Some(expects_key)) = containers.last_mut() {
+                    after_key = *expects_key;
+                    *expects_key = false;
+                }
+            },
+            ',' => {
+                let next = input[offset + 1..].trim_start().chars().next();
+                if matches!(next, None | Some(']' | '}')) {
+                    repairs.push(Repair::TrailingComma { offset });
+                    continue;
+                }
+                if let Some(Some(expects_key)) = containers.last_mut() {
+                    *expects_key = true;
+                }
+                r.push(ch);
+            },
+            '{' => {
+                containers.push(Some(true));
+                r.push(ch);
+            },
+            '[' => {
+                containers.push(None);
+                r.push(ch);
+            },
+            ']' | '}' => {
+                containers.pop();
+                r.push(ch);
+            },
+            _ if ch.is_alphabetic() || ch == '_' || ch == '$' => {
+                let mut end = offset + ch.len_utf8();
+                while let Some((i, c)) = chars.peek().copied() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &input[offset..end];
+                match containers.last_mut() {
+                    Some(Some(expects_key)) if *expects_key => {
+                        repairs.push(Repair::UnquotedKey { offset });
+                        r.push_str(&Value::String(word.to_owned()).to_string());
+                        *expects_key = false;
+                        after_key = true;
+                    },
+                    _ => r.push_str(word),
+                }
+            },
+            _ => r.push(ch),
+        }
+    }
+    if !containers.is_empty() {
+        if after_key {
+            return Err(RepairError::MissingValue { offset: input.len() });
+        }
+        for container in containers.into_iter().rev() {
+            repairs.push(Repair::MissingClose { offset: input.len() });
+            r.push(if container.is_some() { '}' } else { ']' });
+        }
+    }
+    let value = serde_json::from_str(&r).map_err(|e| RepairError::Json { message: e.to_string() })?;
+    Ok((value, repairs))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn unquoted_keys() {
+        assert_eq!(Ok((json!({"a": 1, "b_2": {"$c": true}}), vec![
+            Repair::UnquotedKey { offset: 1 },
+            Repair::UnquotedKey { offset: 7 },
+            Repair::UnquotedKey { offset: 13 },
+        ])), repair_reporting("{a: 1, b_2: {$c: true}}"));
+        assert_eq!(Ok(json!([true, null, {"null": false}])), repair("[true, null, {null: false}]"));
+    }
+
+    #[test]
+    fn single_quotes() {
+        assert_eq!(Ok((json!({"k": "it's \"x\""}), vec![
+            Repair::SingleQuotes { offset: 1 },
+            Repair::SingleQuotes { offset: 6 },
+        ])), repair_reporting(r#"{'k': 'it\'s "x"'}"#));
+        assert_eq!(Ok(json!(["a'b", "\n"])), repair(r#"["a'b", '\n']"#));
+        assert_eq!(Ok(json!(["x'y", "x'y"])), repair(r#"["x\'y", 'x\'y']"#));
+    }
+
+    #[test]
+    fn trailing_commas() {
+        assert_eq!(Ok((json!({"a": [1, 2]}), vec![
+            Repair::TrailingComma { offset: 11 },
+            Repair::TrailingComma { offset: 13 },
+        ])), repair_reporting(r#"{"a": [1, 2,], }"#));
+        assert_eq!(Ok(json!({"a": ",]"})), repair(r#"{"a": ",]"}"#));
+    }
+
+    #[test]
+    fn missing_closes() {
+        assert_eq!(Ok((json!({"a": [1, {"b": 2}]}), vec![
+            Repair::MissingClose { offset: 17 },
+            Repair::MissingClose { offset: 17 },
+            Repair::MissingClose { offset: 17 },
+        ])), repair_reporting(r#"{"a": [1, {"b": 2"#));
+        assert_eq!(Ok((json!([1]), vec![
+            Repair::TrailingComma { offset: 2 },
+            Repair::MissingClose { offset: 4 },
+        ])), repair_reporting("[1, "));
+        assert_eq!(Ok((json!({"a": 1}), vec![])), repair_reporting(r#"{"a": 1}"#));
+    }
+
+    #[test]
+    fn unrepairable() {
+        assert!(matches!(repair("{a: undefined}"), Err(RepairError::Json { .. })));
+        assert_eq!(Err(RepairError::UnterminatedString { offset: 6 }), repair(r#"{"a": "x"#));
+        assert_eq!(Err(RepairError::MissingValue { offset: 5 }), repair(r#"{"a":"#));
+        assert_eq!(Err(RepairError::MissingValue { offset: 4 }), repair(r#"{"a""#));
+        assert_eq!(Err(RepairError::MissingValue { offset: 10 }), repair("{a: 1, b  "));
+        assert!(matches!(repair("[1 2]"), Err(RepairError::Json { .. })));
+        assert!(matches!(repair(""), Err(RepairError::Json { .. })));
+    }
+}
@@ -0,0 +1,162 @@
+use super::lexer::{self, Token};
+use super::Value;
+
+/// Options for [`round_trips_identically`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RoundTripOptions {
+    /// Serializes using the pretty printer instead of the compact one.
+    pub pretty: bool,
+    /// Ignores whitespace outside strings when comparing.
+    pub ignore_whitespace: bool,
+    /// Ignores the order of object keys when comparing. This comparison
+    /// is structural, therefore it also ignores whitespace outside strings.
+    pub ignore_key_order: bool,
+}
+
+/// Checks whether a JSON string is reproduced byte-identically
+/// after being parsed into a [`Value`] and serialized again.
+///
+/// This is useful for verifying that a pipeline is lossless, since
+/// numbers such as `1e2` or `0.10` and escape sequences such as `\u0041`
+/// are not preserved. Invalid JSON never round-trips.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, RoundTripOptions};
+/// assert!(json::round_trips_identically(r#"{"a":[1,2.5]}"#, RoundTripOptions::default()));
+/// assert!(!json::round_trips_identically(r#"{"a":1e2}"#, RoundTripOptions::default()));
+/// ```
+pub fn round_trips_identically(input: &str, opts: RoundTripOptions) -> bool {
+    let Ok(value) = serde_json::from_str::<Value>(input) else {
+        return false;
+    };
+    let output = if opts.pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+    let Ok(output) = output else {
+        return false;
+    };
+    if opts.ignore_key_order {
+        let (Some(input), Some(output)) = (RawValue::parse(input), RawValue::parse(&output)) else {
+            return false;
+        };
+        return input.canonical() == output.canonical();
+    }
+    if opts.ignore_whitespace {
+        return strip_whitespace(input) == strip_whitespace(&output);
+    }
+    input == output
+}
+
+/// Removes whitespace outside strings by joining the text of every
+/// token, or returns `None` if the input contains an invalid token.
+fn strip_whitespace(input: &str) -> Option<String> {
+    lexer::tokens(input).map(|token| token.map(|(_, span)| &input[span])).collect::<Result<String, _>>().ok()
+}
+
+type Tokens = std::iter::Peekable<std::vec::IntoIter<(Token, std::ops::Range<usize>)>>;
+
+/// JSON value that retains the original text of its scalars.
+enum RawValue<'a> {
+    Scalar(&'a str),
+    Array(Vec<RawValue<'a>>),
+    Object(Vec<(&'a str, RawValue<'a>)>),
+}
+
+impl<'a> RawValue<'a> {
+    fn parse(input: &'a str) -> Option<Self> {
+        let tokens: Vec<_> = lexer::tokens(input).collect::<Result<_, _>>().ok()?;
+        let mut tokens = tokens.into_iter().peekable();
+        let r = Self::parse_value(input, &mut tokens)?;
+        if tokens.next().is_none() { Some(r) } else { None }
+    }
+
+    fn parse_value(input: &'a str, tokens: &mut Tokens) -> Option<Self> {
+        let (token, span) = tokens.next()?;
+        match token {
+            Token::BeginObject => {
+                let mut members = Vec::new();
+                if tokens.next_if(|(token, _)| *token == Token::EndObject).is_some() {
+                    return Some(Self::Object(members));
+                }
+                loop {
+                    let (Token::String(_), key) = tokens.next()? else {
+                        return None;
+                    };
+                    tokens.next_if(|(token, _)| *token == Token::Colon)?;
+                    members.push((&input[key], Self::parse_value(input, tokens)?));
+                    match tokens.next()?.0 {
+                        Token::Comma => {},
+                        Token::EndObject => return Some(Self::Object(members)),
+                        _ => return None,
+                    }
+                }
+            },
+            Token::BeginArray => {
+                let mut elements = Vec::new();
+                if tokens.next_if(|(token, _)| *token == Token::EndArray).is_some() {
+                    return Some(Self::Array(elements));
+                }
+                loop {
+                    elements.push(Self::parse_value(input, tokens)?);
+                    match tokens.next()?.0 {
+                        Token::Comma => {},
+                        Token::EndArray => return Some(Self::Array(elements)),
+                        _ => return None,
+                    }
+                }
+            },
+            Token::String(_) | Token::Number(_) | Token::Bool(_) | Token::Null => Some(Self::Scalar(&input[span])),
+            _ => None,
+        }
+    }
+
+    /// Returns compact text with object members sorted by key.
+    fn canonical(&self) -> String {
+        match self {
+            Self::Scalar(s) => (*s).to_owned(),
+            Self::Array(elements) => {
+                "[".to_owned() + &elements.iter().map(|e| e.canonical()).collect::<Vec<_>>().join(",") + "]"
+            },
+            Self::Object(members) => {
+                let mut members: Vec<(String, String)> = members.iter().map(|(k, v)| {
+                    (serde_json::from_str::<String>(k).unwrap_or_default(), (*k).to_owned() + ":" + &v.canonical())
+                }).collect();
+                members.sort();
+                "{".to_owned() + &members.into_iter().map(|(_, m)| m).collect::<Vec<_>>().join(",") + "}"
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let default = RoundTripOptions::default();
+        assert!(round_trips_identically(r#"{"a":[1,-2.5,"x\"y"],"b":null}"#, default));
+        assert!(!round_trips_identically(r#"{"a":1.0e2}"#, default));
+        assert!(!round_trips_identically(r#"{"a":"\u0041"}"#, default));
+        assert!(!round_trips_identically(r#"{"a": 1}"#, default));
+        assert!(!round_trips_identically("{", default));
+
+        let ignore_whitespace = RoundTripOptions { ignore_whitespace: true, ..default };
+        assert!(round_trips_identically("{ \"a\" : [ 1 , 2 ] }", ignore_whitespace));
+        assert!(round_trips_identically(r#"{"a":"x y"}"#, ignore_whitespace));
+        assert!(!round_trips_identically("[ 1.50 ]", ignore_whitespace));
+        assert!(!round_trips_identically(r#"{"b":1,"a":2}"#, ignore_whitespace));
+
+        let ignore_key_order = RoundTripOptions { ignore_key_order: true, ..default };
+        assert!(round_trips_identically(r#"{"b":1,"a":{"d":2,"c":3}}"#, ignore_key_order));
+        assert!(!round_trips_identically(r#"{"b":1,"a":0.10}"#, ignore_key_order));
+        assert!(round_trips_identically("{ \"b\" : [true, null], \"a\" : \"x\\\"y\" }", ignore_key_order));
+
+        let pretty = RoundTripOptions { pretty: true, ..default };
+        assert!(round_trips_identically("{\n  \"a\": 1\n}", pretty));
+    }
+}
@@ -0,0 +1,185 @@
+use super::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The types observed at a position of the sample data.
+#[derive(Default)]
+struct Inferred {
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    string: bool,
+    /// The inferred element type of every array seen, if any array
+    /// was seen. `Some(None)` means that all arrays were empty.
+    array: Option<Option<Box<Inferred>>>,
+    /// The properties of every object seen, and the keys present
+    /// in all of them, if any object was seen.
+    object: Option<(BTreeMap<String, Inferred>, BTreeSet<String>)>,
+}
+
+impl Inferred {
+    fn of(value: &Value) -> Self {
+        let mut r = Self::default();
+        match value {
+            Value::Null => r.null = true,
+            Value::Bool(_) => r.boolean = true,
+            Value::Number(n) => if n.is_f64() { r.number = true } else { r.integer = true },
+            Value::String(_) => r.string = true,
+            Value::Array(elements) => {
+                let items = elements.iter().map(Self::of).reduce(Self::merge);
+                r.array = Some(items.map(Box::new));
+            },
+            Value::Object(fields) => {
+                let properties = fields.iter().map(|(k, v)| (k.clone(), Self::of(v))).collect();
+                r.object = Some((properties, fields.keys().cloned().collect()));
+            },
+        }
+        r
+    }
+
+    fn merge(self, other: Self) -> Self {
+        let array = match (self.array, other.array) {
+            (Some(Some(a)), Some(Some(b))) => Some(Some(Box::new(a.merge(*b)))),
+            (Some(a), Some(b)) => Some(a.or(b)),
+            (a, b) => a.or(b),
+        };
+        let object = match (self.object, other.object) {
+            (Some((mut properties, required)), Some((other_properties, other_required))) => {
+                for (k, v) in other_properties {
+                    let merged = match properties.remove(&k) {
+                        Some(existing) => existing.merge(v),
+                        None => v,
+                    };
+                    properties.insert(k, merged);
+                }
+                Some((properties, required.intersection(&other_required).cloned().collect()))
+            },
+            (a, b) => a.or(b),
+        };
+        Self {
+            null: self.null || other.null,
+            boolean: self.boolean || other.boolean,
+            integer: self.integer || other.integer,
+            number: self.number || other.number,
+            string: self.string || other.string,
+            array,
+            object,
+        }
+    }
+
+    fn to_schema(&self) -> Value {
+        let mut types = Vec::<Value>::new();
+        let mut r = Map::new();
+        if let Some(items) = &self.array {
+            types.push(json!("array"));
+            if let Some(items) = items {
+                r.insert("items".into(), items.to_schema());
+            }
+        }
+        if self.boolean {
+            types.push(json!("boolean"));
+        }
+        if self.integer && !self.number {
+            types.push(json!("integer"));
+        }
+        if self.null {
+            types.push(json!("null"));
+        }
+        if self.number {
+            types.push(json!("number"));
+        }
+        if let Some((properties, required)) = &self.object {
+            types.push(json!("object"));
+            r.insert("properties".into(), Value::Object(properties.iter().map(|(k, v)| (k.clone(), v.to_schema())).collect()));
+            if !required.is_empty() {
+                r.insert("required".into(), required.iter().map(|k| json!(k)).collect());
+            }
+        }
+        if self.string {
+            types.push(json!("string"));
+        }
+        let types = if types.len() == 1 { types.remove(0) } else { Value::Array(types) };
+        r.insert("type".into(), types);
+        Value::Object(r)
+    }
+}
+
+/// Infers a minimal JSON Schema describing the types of an example value.
+///
+/// Objects are described by their `properties` and the keys they contain as
+/// `required`, and arrays by the `items` schema of all their elements merged
+/// together. When elements differ, `type` lists every type seen, properties
+/// are united and only keys present in every object remain `required`.
+/// An integer and a non-integer number merge into `number`.
+///
+/// The result is a starting point that usually needs manual refinement.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(
+///     json!({"type": "array", "items": {"type": ["integer", "string"]}}),
+///     json::infer_schema(&json!([1, "a"])),
+/// );
+/// ```
+pub fn infer_schema(value: &Value) -> Value {
+    Inferred::of(value).to_schema()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nested_sample() {
+        let sample = json!({
+            "name": "app",
+            "version": 1,
+            "ratio": 0.5,
+            "tags": [],
+            "owner": null,
+            "servers": [
+                {"host": "a", "port": 80, "tls": true},
+                {"host": "b", "port": 8.5},
+            ],
+        });
+        assert_eq!(json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "owner": {"type": "null"},
+                "ratio": {"type": "number"},
+                "servers": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "host": {"type": "string"},
+                            "port": {"type": "number"},
+                            "tls": {"type": "boolean"},
+                        },
+                        "required": ["host", "port"],
+                    },
+                },
+                "tags": {"type": "array"},
+                "version": {"type": "integer"},
+            },
+            "required": ["name", "owner", "ratio", "servers", "tags", "version"],
+        }), infer_schema(&sample));
+    }
+
+    #[test]
+    fn mixed_types() {
+        assert_eq!(json!({"type": "string"}), infer_schema(&json!("x")));
+        assert_eq!(json!({"type": "object", "properties": {}}), infer_schema(&json!({})));
+        assert_eq!(
+            json!({"type": "array", "items": {"type": ["array", "null", "object"], "items": {"type": "integer"}, "properties": {}}}),
+            infer_schema(&json!([null, [], [1], {}])),
+        );
+        assert_eq!(
+            json!({"type": "array", "items": {"type": "object", "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}}}}),
+            infer_schema(&json!([{"a": 1}, {"b": 2}])),
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use super::Value;
+use std::mem::size_of;
+
+/// Estimates the number of heap bytes used by a value, such as for
+/// bounding an in-memory cache of parsed documents. The estimate is
+/// rough and is only meant for comparing values.
+///
+/// The estimate sums, recursively, the capacity of strings and keys,
+/// the slots of arrays and the entries of objects. A number, boolean
+/// or `null` occupies only its slot in the containing array or object,
+/// so a scalar at the root uses no heap.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(0, json::estimated_heap_size(&json!(1)));
+/// assert!(json::estimated_heap_size(&json!(["a", "b"])) > json::estimated_heap_size(&json!(["a"])));
+/// ```
+pub fn estimated_heap_size(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => 0,
+        Value::String(s) => s.capacity(),
+        Value::Array(a) => a.capacity() * size_of::<Value>() + a.iter().map(estimated_heap_size).sum::<usize>(),
+        Value::Object(o) => o.iter().map(|(k, v)| {
+            size_of::<String>() + size_of::<Value>() + k.capacity() + estimated_heap_size(v)
+        }).sum(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn heap_size() {
+        assert_eq!(0, estimated_heap_size(&json!(null)));
+        assert_eq!(0, estimated_heap_size(&json!(1.5)));
+        assert_eq!(0, estimated_heap_size(&json!([])));
+        assert!(estimated_heap_size(&json!("abcdef")) >= 6);
+
+        let small = json!({"name": "a", "tags": ["x"]});
+        let large = json!({
+            "name": "a much longer name",
+            "tags": ["x", "y", "z"],
+            "nested": {"values": [1, 2, 3, 4], "flag": true},
+        });
+        assert!(estimated_heap_size(&small) > 0);
+        assert!(estimated_heap_size(&large) > estimated_heap_size(&small));
+
+        let items: Vec<_> = (0..100).map(|i| json!({"id": i})).collect();
+        let many = json!(items);
+        let few = json!(items[..10]);
+        assert!(estimated_heap_size(&many) > estimated_heap_size(&few));
+    }
+}
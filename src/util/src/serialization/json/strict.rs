@@ -0,0 +1,95 @@
+use super::lexer::{self, Token};
+use super::super::generic_deserialization::DeserializeOwned;
+
+/// The `DuplicateKeyError` enumeration represents an error that occurred
+/// while deserializing JSON that must not contain duplicate keys.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DuplicateKeyError {
+    /// An object contains the same key more than once. The line and
+    /// the column in bytes, both starting at 1, locate the repeated key.
+    Duplicate {
+        key: String,
+        line: usize,
+        column: usize,
+    },
+    /// The JSON is invalid or does not match the target type.
+    Json {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for DuplicateKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Duplicate { key, line, column } => write!(f, "Duplicate key {:?} at line {} column {}", key, line, column),
+            Self::Json { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for DuplicateKeyError {}
+
+/// Deserializes a JSON string into a value, failing if any object
+/// contains a repeated key, whereas [`deserialize`](super::deserialize)
+/// keeps the last value of a repeated key.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, Value};
+/// assert!(json::deserialize_reject_duplicates::<Value>(r#"{"a": 1, "b": 2}"#).is_ok());
+/// assert!(json::deserialize_reject_duplicates::<Value>(r#"{"a": 1, "a": 2}"#).is_err());
+/// ```
+pub fn deserialize_reject_duplicates<T>(string: &str) -> Result<T, DuplicateKeyError>
+    where T: DeserializeOwned
+{
+    // each open container is an object with its keys and whether a
+    // key is expected next, or an array
+    let mut containers = Vec::<Option<(crate::collections::Set<String>, bool)>>::new();
+    for token in lexer::tokens(string) {
+        // a lexical error is reported by the parser below
+        let Ok((token, span)) = token else {
+            break;
+        };
+        match (token, containers.last_mut()) {
+            (Token::BeginObject, _) => containers.push(Some((Default::default(), true))),
+            (Token::BeginArray, _) => containers.push(None),
+            (Token::EndObject | Token::EndArray, _) => {
+                containers.pop();
+            },
+            (Token::Comma, Some(Some((_, expects_key)))) => *expects_key = true,
+            (Token::String(key), Some(Some((keys, expects_key)))) if *expects_key => {
+                *expects_key = false;
+                if !keys.insert(key.clone()) {
+                    let before = &string[..span.start];
+                    let line = before.matches('\n').count() + 1;
+                    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+                    return Err(DuplicateKeyError::Duplicate { key, line, column });
+                }
+            },
+            _ => {},
+        }
+    }
+    serde_json::from_str(string).map_err(|e| DuplicateKeyError::Json { message: e.to_string() })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::{json, Value};
+
+    #[test]
+    fn duplicates() {
+        let text = "{\n  \"a\": {\"x\": 1, \"y\": [{\"x\": 2}]},\n  \"x\": 3\n}";
+        assert_eq!(Ok(json!({"a": {"x": 1, "y": [{"x": 2}]}, "x": 3})), deserialize_reject_duplicates::<Value>(text));
+        assert_eq!(Ok(json!({"k": "k"})), deserialize_reject_duplicates::<Value>(r#"{"k": "k"}"#));
+
+        let text = "{\n  \"a\": 1,\n  \"b\": {\"c\": 1, \"c\": 2}\n}";
+        assert_eq!(Err(DuplicateKeyError::Duplicate { key: "c".into(), line: 3, column: 17 }), deserialize_reject_duplicates::<Value>(text));
+        let error = deserialize_reject_duplicates::<Value>(r#"[{"a": 1, "a": 1}]"#).unwrap_err();
+        assert_eq!("Duplicate key \"a\" at line 1 column 11", error.to_string());
+
+        assert!(matches!(deserialize_reject_duplicates::<Value>("{\"a\": }"), Err(DuplicateKeyError::Json { .. })));
+        assert!(matches!(deserialize_reject_duplicates::<u8>("\"x\""), Err(DuplicateKeyError::Json { .. })));
+    }
+}
@@ -0,0 +1,96 @@
+use super::Value;
+
+/// The `TemplateError` enumeration represents an error that
+/// occurred while rendering a template.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TemplateError {
+    /// A placeholder refers to a missing value and gives no default.
+    Missing {
+        pointer: String,
+    },
+    /// A `{` is not closed by a `}`.
+    Unclosed {
+        offset: usize,
+    },
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing { pointer } => write!(f, "No value at {:?}", pointer),
+            Self::Unclosed { offset } => write!(f, "Unclosed placeholder at byte {}", offset),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Renders a template, replacing each `{pointer}` placeholder by the value
+/// at that JSON Pointer in `context`. A leading `/` may be omitted, so that
+/// `{name}` and `{/name}` are equivalent and `{user/name}` looks up a nested
+/// value.
+///
+/// Strings are inserted as they are and other values are JSON-encoded.
+/// A missing value is an error unless the placeholder gives a default, as in
+/// `{name?anonymous}`. `{{` and `}}` produce literal braces.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let context = json!({"user": {"name": "Ada"}});
+/// assert_eq!(Ok("Hello Ada, 0 new".to_owned()), json::render_template("Hello {user/name}, {count?0} new", &context));
+/// ```
+pub fn render_template(template: &str, context: &Value) -> Result<String, TemplateError> {
+    let mut r = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        r.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        if rest[i..].starts_with("{{") || rest[i..].starts_with("}}") {
+            r.push_str(&rest[i..i + 1]);
+            rest = &after[1..];
+            continue;
+        }
+        if rest.as_bytes()[i] == b'}' {
+            r.push('}');
+            rest = after;
+            continue;
+        }
+        let end = after.find('}').ok_or(TemplateError::Unclosed { offset: template.len() - rest.len() + i })?;
+        let (pointer, default) = match after[..end].split_once('?') {
+            Some((pointer, default)) => (pointer, Some(default)),
+            None => (&after[..end], None),
+        };
+        let pointer = if pointer.starts_with('/') { pointer.to_owned() } else { "/".to_owned() + pointer };
+        match (context.pointer(&pointer), default) {
+            (Some(Value::String(s)), _) => r.push_str(s),
+            (Some(value), _) => r.push_str(&value.to_string()),
+            (None, Some(default)) => r.push_str(default),
+            (None, None) => return Err(TemplateError::Missing { pointer }),
+        }
+        rest = &after[end + 1..];
+    }
+    r.push_str(rest);
+    Ok(r)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn render() {
+        let context = json!({"name": "Ada", "user": {"roles": ["admin", "dev"], "age": 36, "a/b": true}});
+        assert_eq!(Ok("Hello Ada".to_owned()), render_template("Hello {name}", &context));
+        assert_eq!(Ok("dev, 36, true".to_owned()), render_template("{user/roles/1}, {/user/age}, {user/a~1b}", &context));
+        assert_eq!(Ok(r#"["admin","dev"]"#.to_owned()), render_template("{user/roles}", &context));
+        assert_eq!(Ok("Hi guest!".to_owned()), render_template("Hi {nick?guest}!", &context));
+        assert_eq!(Ok("Ada".to_owned()), render_template("{name?x}", &context));
+        assert_eq!(Ok("[]".to_owned()), render_template("[{missing?}]", &context));
+        assert_eq!(Ok("{name} } Ada".to_owned()), render_template("{{name}} } {name}", &context));
+        assert_eq!(Err(TemplateError::Missing { pointer: "/user/email".into() }), render_template("{user/email}", &context));
+        assert_eq!(Err(TemplateError::Unclosed { offset: 5 }), render_template("ab {{{name", &context));
+    }
+}
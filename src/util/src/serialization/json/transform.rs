@@ -0,0 +1,334 @@
+use super::{ConflictPolicy, KeyConflictError, Map, Number, Value};
+
+/// Indicates which representation [`canonicalize_numbers`] gives
+/// to numbers that can be represented both as integers and floats.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberPolicy {
+    /// Whole floats, such as `1.0`, become integers.
+    PreferInteger,
+    /// Integers become floats.
+    PreferFloat,
+}
+
+/// Rewrites every number in a value according to `policy`.
+///
+/// Under `PreferInteger`, whole floats out of the 64-bit integer range
+/// are left as floats. Under `PreferFloat`, integers of magnitude above
+/// 2<sup>53</sup> may lose precision.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json, NumberPolicy};
+/// let mut value = json!({"a": 1.0, "b": [2.0, 2.5]});
+/// json::canonicalize_numbers(&mut value, NumberPolicy::PreferInteger);
+/// assert_eq!(r#"{"a":1,"b":[2,2.5]}"#, value.to_string());
+/// ```
+pub fn canonicalize_numbers(value: &mut Value, policy: NumberPolicy) {
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Array(elements) => stack.extend(elements.iter_mut()),
+            Value::Object(fields) => stack.extend(fields.values_mut()),
+            Value::Number(n) => {
+                if let Some(r) = canonical_number(n, policy) {
+                    *n = r;
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+fn canonical_number(n: &Number, policy: NumberPolicy) -> Option<Number> {
+    match policy {
+        NumberPolicy::PreferInteger => {
+            let f = n.as_f64().filter(|_| n.is_f64())?;
+            if f.fract() != 0.0 {
+                None
+            } else if f >= i64::MIN as f64 && f < i64::MAX as f64 {
+                Some((f as i64).into())
+            } else if f >= 0.0 && f < u64::MAX as f64 {
+                Some((f as u64).into())
+            } else {
+                None
+            }
+        },
+        NumberPolicy::PreferFloat => {
+            if n.is_f64() { None } else { Number::from_f64(n.as_f64()?) }
+        },
+    }
+}
+
+/// Renames object keys throughout a value, at any depth, leaving values
+/// untouched. Each pair of `renames` gives an old key and its new name;
+/// renames apply simultaneously, so swapping two keys is possible.
+///
+/// When a new name collides with another key of the same object, keys
+/// are taken in the object's iteration order and the last one wins.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut value = json!({"userName": "a", "friends": [{"userName": "b"}]});
+/// json::rename_keys(&mut value, &[("userName", "user_name")]);
+/// assert_eq!(json!({"user_name": "a", "friends": [{"user_name": "b"}]}), value);
+/// ```
+pub fn rename_keys(value: &mut Value, renames: &[(&str, &str)]) {
+    rename_keys_with_policy(value, renames, ConflictPolicy::LastWins).unwrap()
+}
+
+/// Renames object keys throughout a value, handling collisions according
+/// to `policy`. See [`rename_keys`]. Under `ConflictPolicy::Error`, the
+/// value is left unchanged if any collision is found.
+pub fn rename_keys_with_policy(value: &mut Value, renames: &[(&str, &str)], policy: ConflictPolicy) -> Result<(), KeyConflictError> {
+    let rename = |key: &str| renames.iter().find(|(old, _)| *old == key).map_or(key, |(_, new)| *new).to_owned();
+    if policy == ConflictPolicy::Error {
+        let mut stack = vec![&*value];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Array(elements) => stack.extend(elements.iter()),
+                Value::Object(fields) => {
+                    let mut seen = crate::collections::Set::<String>::new();
+                    for key in fields.keys().map(|k| rename(k)) {
+                        if !seen.insert(key.clone()) {
+                            return Err(KeyConflictError { key });
+                        }
+                    }
+                    stack.extend(fields.values());
+                },
+                _ => {},
+            }
+        }
+    }
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Array(elements) => stack.extend(elements.iter_mut()),
+            Value::Object(fields) => {
+                let renamed: Map<String, Value> = std::mem::take(fields).into_iter().map(|(k, v)| (rename(&k), v)).collect();
+                *fields = renamed;
+                stack.extend(fields.values_mut());
+            },
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+/// Maps each element of an array into a new array, or returns `None`
+/// if the value is not an array.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let doubled = json::map_array(&json!([1, 2]), |v| json!(v.as_i64().unwrap() * 2));
+/// assert_eq!(Some(json!([2, 4])), doubled);
+/// ```
+pub fn map_array<F: FnMut(&Value) -> Value>(value: &Value, f: F) -> Option<Value> {
+    Some(Value::Array(value.as_array()?.iter().map(f).collect()))
+}
+
+/// Returns a new array with the elements of an array for which `f`
+/// returns `true`, or `None` if the value is not an array.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// assert_eq!(Some(json!(["a"])), json::filter_array(&json!(["a", 1]), |v| v.is_string()));
+/// ```
+pub fn filter_array<F: FnMut(&Value) -> bool>(value: &Value, mut f: F) -> Option<Value> {
+    Some(Value::Array(value.as_array()?.iter().filter(|v| f(v)).cloned().collect()))
+}
+
+/// A letter case style for object keys, used by [`transform_keys`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyStyle {
+    /// Such as `userName`.
+    CamelCase,
+    /// Such as `user_name`.
+    SnakeCase,
+    /// Such as `user-name`.
+    KebabCase,
+    /// Such as `UserName`.
+    PascalCase,
+}
+
+/// Converts every object key throughout a value, at any depth, to the
+/// given letter case style, leaving values untouched.
+///
+/// Keys are split into words at `_` and `-` and before an uppercase
+/// letter that follows a lowercase letter or a digit, or that starts
+/// a word after an uppercase run, as in `HTTPServer`. Digits belong to
+/// the preceding word, so `address_line1` and `addressLine1` convert
+/// into each other. Leading `_` and `-` characters are kept.
+///
+/// When converted keys collide within an object, keys are taken in the
+/// object's iteration order and the last one wins.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json, KeyStyle};
+/// let mut value = json!({"user_name": "a", "friend_list": [{"user_name": "b"}]});
+/// json::transform_keys(&mut value, KeyStyle::CamelCase);
+/// assert_eq!(json!({"userName": "a", "friendList": [{"userName": "b"}]}), value);
+/// ```
+pub fn transform_keys(value: &mut Value, style: KeyStyle) {
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Array(elements) => stack.extend(elements.iter_mut()),
+            Value::Object(fields) => {
+                let converted: Map<String, Value> = std::mem::take(fields).into_iter().map(|(k, v)| (convert_key(&k, style), v)).collect();
+                *fields = converted;
+                stack.extend(fields.values_mut());
+            },
+            _ => {},
+        }
+    }
+}
+
+fn convert_key(key: &str, style: KeyStyle) -> String {
+    let rest = key.trim_start_matches(['_', '-']);
+    let mut r = key[..key.len() - rest.len()].to_owned();
+
+    let chars: Vec<char> = rest.chars().collect();
+    let mut words = Vec::<String>::new();
+    let mut word = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == '_' || ch == '-' {
+            words.extend(Some(std::mem::take(&mut word)).filter(|w| !w.is_empty()));
+            continue;
+        }
+        if ch.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lowercase) {
+                words.extend(Some(std::mem::take(&mut word)).filter(|w| !w.is_empty()));
+            }
+        }
+        word.extend(ch.to_lowercase());
+    }
+    words.extend(Some(word).filter(|w| !w.is_empty()));
+
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+    };
+    for (i, word) in words.iter().enumerate() {
+        match style {
+            KeyStyle::CamelCase if i == 0 => r.push_str(word),
+            KeyStyle::CamelCase | KeyStyle::PascalCase => r.push_str(&capitalize(word)),
+            KeyStyle::SnakeCase | KeyStyle::KebabCase => {
+                if i != 0 {
+                    r.push(if style == KeyStyle::SnakeCase { '_' } else { '-' });
+                }
+                r.push_str(word);
+            },
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn key_styles() {
+        let snake = json!({
+            "user_name": "Some_Value",
+            "address_line1": {"zip_code": 1, "v2_api_key": [{"is_http_server": true}]},
+            "_private_id": null,
+            "id": 2,
+        });
+        let camel = json!({
+            "userName": "Some_Value",
+            "addressLine1": {"zipCode": 1, "v2ApiKey": [{"isHttpServer": true}]},
+            "_privateId": null,
+            "id": 2,
+        });
+        let mut value = snake.clone();
+        transform_keys(&mut value, KeyStyle::CamelCase);
+        assert_eq!(camel, value);
+        transform_keys(&mut value, KeyStyle::SnakeCase);
+        assert_eq!(snake, value);
+
+        assert_eq!("UserName", convert_key("user-name", KeyStyle::PascalCase));
+        assert_eq!("user-name", convert_key("UserName", KeyStyle::KebabCase));
+        assert_eq!("http_server", convert_key("HTTPServer", KeyStyle::SnakeCase));
+        assert_eq!("parseJson2Xml", convert_key("parse_JSON2_xml", KeyStyle::CamelCase));
+        assert_eq!("line2", convert_key("line2", KeyStyle::SnakeCase));
+        assert_eq!("a_b", convert_key("a__b", KeyStyle::SnakeCase));
+        assert_eq!("", convert_key("", KeyStyle::CamelCase));
+
+        let mut value = json!({"a_b": 1, "aB": 2});
+        transform_keys(&mut value, KeyStyle::CamelCase);
+        assert_eq!(json!({"aB": 1}), value);
+    }
+
+    #[test]
+    fn renaming() {
+        let mut value = json!({
+            "id": 1,
+            "meta": {"id": "x", "tags": [{"id": 2, "name": "t"}]},
+            "list": [[{"id": null}]],
+        });
+        rename_keys(&mut value, &[("id", "key"), ("name", "label")]);
+        assert_eq!(json!({
+            "key": 1,
+            "meta": {"key": "x", "tags": [{"key": 2, "label": "t"}]},
+            "list": [[{"key": null}]],
+        }), value);
+
+        let mut value = json!({"a": 1, "b": 2});
+        rename_keys(&mut value, &[("a", "b"), ("b", "a")]);
+        assert_eq!(json!({"a": 2, "b": 1}), value);
+
+        let mut value = json!({"x": {"a": 1, "b": 2}});
+        rename_keys(&mut value, &[("a", "b")]);
+        assert_eq!(json!({"x": {"b": 2}}), value);
+
+        let mut value = json!({"x": {"a": 1, "b": 2}, "a": 0});
+        assert_eq!(Err(KeyConflictError { key: "b".into() }), rename_keys_with_policy(&mut value, &[("a", "b")], ConflictPolicy::Error));
+        assert_eq!(json!({"x": {"a": 1, "b": 2}, "a": 0}), value);
+        assert_eq!(Ok(()), rename_keys_with_policy(&mut value, &[("x", "y")], ConflictPolicy::Error));
+        assert_eq!(json!({"y": {"a": 1, "b": 2}, "a": 0}), value);
+    }
+
+    #[test]
+    fn array_combinators() {
+        let value = json!([1, 2.5, 3, "x"]);
+        let squared = map_array(&value, |v| v.as_f64().map(|n| json!(n * n)).unwrap_or(Value::Null));
+        assert_eq!(Some(json!([1.0, 6.25, 9.0, null])), squared);
+        assert_eq!(Some(json!([2.5, 3])), filter_array(&value, |v| v.as_f64().is_some_and(|n| n > 2.0)));
+        assert_eq!(Some(json!([])), filter_array(&json!([]), |_| true));
+
+        let mut visited = 0;
+        assert_eq!(Some(json!([null, null])), map_array(&json!([{}, []]), |_| { visited += 1; Value::Null }));
+        assert_eq!(2, visited);
+
+        assert_eq!(None, map_array(&json!({"a": 1}), |v| v.clone()));
+        assert_eq!(None, filter_array(&json!("a"), |_| true));
+    }
+
+    #[test]
+    fn numbers() {
+        let mut value = json!({"a": 1.0, "b": [2.0, {"c": -3.0}], "d": 0.5, "e": 1e300, "f": "1.0"});
+        canonicalize_numbers(&mut value, NumberPolicy::PreferInteger);
+        assert_eq!(r#"{"a":1,"b":[2,{"c":-3}],"d":0.5,"e":1e+300,"f":"1.0"}"#, value.to_string());
+        assert_eq!(json!({"a": 1, "b": [2, {"c": -3}], "d": 0.5, "e": 1e300, "f": "1.0"}), value);
+
+        canonicalize_numbers(&mut value, NumberPolicy::PreferFloat);
+        assert_eq!(r#"{"a":1.0,"b":[2.0,{"c":-3.0}],"d":0.5,"e":1e+300,"f":"1.0"}"#, value.to_string());
+
+        let mut value = json!([u64::MAX]);
+        canonicalize_numbers(&mut value, NumberPolicy::PreferInteger);
+        assert_eq!(json!([u64::MAX]), value);
+    }
+}
@@ -0,0 +1,299 @@
+use super::{pointer, Value};
+use crate::collections::Map;
+
+/// The `DepthLimitError` structure represents an error that
+/// occurred because a value is nested deeper than allowed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DepthLimitError {
+    /// The maximum nesting depth that was exceeded.
+    pub max_depth: usize,
+}
+
+impl std::fmt::Display for DepthLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Value exceeds the maximum nesting depth of {}", self.max_depth)
+    }
+}
+
+impl std::error::Error for DepthLimitError {}
+
+/// Visits every node of a value in pre-order, along with its JSON Pointer.
+///
+/// The root is at depth 0 and the elements of a container are one
+/// level deeper than it. The walk is iterative and fails as soon as
+/// a node deeper than `max_depth` is reached, after which no further
+/// nodes are visited.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut pointers = vec![];
+/// json::walk(&json!({"a": [1]}), 8, |pointer, _| pointers.push(pointer.to_owned())).unwrap();
+/// assert_eq!(vec!["", "/a", "/a/0"], pointers);
+/// assert!(json::walk(&json!([[1]]), 1, |_, _| {}).is_err());
+/// ```
+pub fn walk<'a, F>(value: &'a Value, max_depth: usize, mut visit: F) -> Result<(), DepthLimitError>
+    where F: FnMut(&str, &'a Value)
+{
+    let mut stack = vec![("".to_owned(), value, 0)];
+    while let Some((p, value, depth)) = stack.pop() {
+        if depth > max_depth {
+            return Err(DepthLimitError { max_depth });
+        }
+        visit(&p, value);
+        match value {
+            Value::Array(elements) => {
+                for (i, element) in elements.iter().enumerate().rev() {
+                    stack.push((pointer::push_token(&p, &i.to_string()), element, depth + 1));
+                }
+            },
+            Value::Object(fields) => {
+                for (key, field) in fields.iter().rev() {
+                    stack.push((pointer::push_token(&p, key), field, depth + 1));
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+/// Deeply merges `source` into `target`. Objects are merged key-wise
+/// and any other value in `source`, including arrays and `null`,
+/// replaces the corresponding value in `target`.
+///
+/// Fails if `source` is nested deeper than `max_depth`, in which case
+/// `target` is left unchanged.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let mut target = json!({"a": {"b": 1, "c": 2}});
+/// json::merge(&mut target, &json!({"a": {"c": 3}, "d": [4]}), 8).unwrap();
+/// assert_eq!(json!({"a": {"b": 1, "c": 3}, "d": [4]}), target);
+/// ```
+pub fn merge(target: &mut Value, source: &Value, max_depth: usize) -> Result<(), DepthLimitError> {
+    walk(source, max_depth, |_, _| {})?;
+    merge_unchecked(target, source);
+    Ok(())
+}
+
+fn merge_unchecked(target: &mut Value, source: &Value) {
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, field) in source {
+                match target.get_mut(key) {
+                    Some(target_field) => merge_unchecked(target_field, field),
+                    None => {
+                        target.insert(key.clone(), field.clone());
+                    },
+                }
+            }
+        },
+        (target, source) => *target = source.clone(),
+    }
+}
+
+/// Deeply merges named layers in order, as [`merge`] does, tracking
+/// which layer set each final value.
+///
+/// The returned map associates the JSON Pointer of every final value
+/// that is not an object, including arrays as a whole, with the name of
+/// the layer that set it. Later layers override earlier ones. If there
+/// are no layers, an empty object is returned.
+///
+/// Fails if any layer is nested deeper than `max_depth`, before any
+/// layer is merged.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let layers = [
+///     ("defaults".to_owned(), json!({"port": 80, "host": "localhost"})),
+///     ("user".to_owned(), json!({"port": 8080})),
+/// ];
+/// let (value, sources) = json::merge_tracked(&layers, 8).unwrap();
+/// assert_eq!(json!({"port": 8080, "host": "localhost"}), value);
+/// assert_eq!("user", sources["/port"]);
+/// assert_eq!("defaults", sources["/host"]);
+/// ```
+pub fn merge_tracked(layers: &[(String, Value)], max_depth: usize) -> Result<(Value, Map<String, String>), DepthLimitError> {
+    for (_, layer) in layers {
+        walk(layer, max_depth, |_, _| {})?;
+    }
+    let mut target = Value::Object(super::Map::new());
+    let mut sources = Map::<String, String>::new();
+    for (name, layer) in layers {
+        merge_tracked_at(&mut target, layer, "", name, &mut sources);
+    }
+    Ok((target, sources))
+}
+
+fn merge_tracked_at(target: &mut Value, source: &Value, p: &str, name: &str, sources: &mut Map<String, String>) {
+    if let (Value::Object(target), Value::Object(source)) = (&mut *target, source) {
+        for (key, field) in source {
+            let p = pointer::push_token(p, key);
+            match target.get_mut(key) {
+                Some(target_field) => merge_tracked_at(target_field, field, &p, name, sources),
+                None => {
+                    target.insert(key.clone(), field.clone());
+                    track(field, &p, name, sources);
+                },
+            }
+        }
+        return;
+    }
+    let descendants = p.to_owned() + "/";
+    sources.retain(|k, _| k != p && !k.starts_with(&descendants));
+    *target = source.clone();
+    track(source, p, name, sources);
+}
+
+fn track(value: &Value, p: &str, name: &str, sources: &mut Map<String, String>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                track(field, &pointer::push_token(p, key), name, sources);
+            }
+        },
+        _ => {
+            sources.insert(p.to_owned(), name.to_owned());
+        },
+    }
+}
+
+/// Returns every string in a value, along with its JSON Pointer,
+/// in the order [`walk`] visits them.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::json::{self, json};
+/// let value = json!({"title": "Hi", "tags": ["a", 1]});
+/// assert_eq!(vec![("/tags/0".to_owned(), "a"), ("/title".to_owned(), "Hi")], json::string_leaves(&value));
+/// ```
+pub fn string_leaves(value: &Value) -> Vec<(String, &str)> {
+    let mut r = Vec::<(String, &str)>::new();
+    let _ = walk(value, usize::MAX, |p, v| {
+        if let Value::String(s) = v {
+            r.push((p.to_owned(), s.as_str()));
+        }
+    });
+    r
+}
+
+/// Returns every string in a value as [`string_leaves`] does,
+/// additionally including object keys. A key is paired with the
+/// JSON Pointer of its member and precedes the member's strings.
+pub fn string_leaves_with_keys(value: &Value) -> Vec<(String, &str)> {
+    let mut r = Vec::<(String, &str)>::new();
+    let _ = walk(value, usize::MAX, |p, v| match v {
+        Value::String(s) => r.push((p.to_owned(), s.as_str())),
+        Value::Object(fields) => {
+            for key in fields.keys() {
+                r.push((pointer::push_token(p, key), key.as_str()));
+            }
+        },
+        _ => {},
+    });
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::json;
+
+    #[test]
+    fn walking() {
+        let value = json!({"a": {"b~": [true, null]}, "c": 1});
+        let mut visited = vec![];
+        walk(&value, 3, |p, v| visited.push((p.to_owned(), v.clone()))).unwrap();
+        assert_eq!(vec![
+            ("".to_owned(), value.clone()),
+            ("/a".to_owned(), json!({"b~": [true, null]})),
+            ("/a/b~0".to_owned(), json!([true, null])),
+            ("/a/b~0/0".to_owned(), json!(true)),
+            ("/a/b~0/1".to_owned(), json!(null)),
+            ("/c".to_owned(), json!(1)),
+        ], visited);
+        assert_eq!(Err(DepthLimitError { max_depth: 2 }), walk(&value, 2, |_, _| {}));
+    }
+
+    #[test]
+    fn merging() {
+        let mut target = json!({"a": {"b": [1, 2], "c": 1}, "x": 1});
+        merge(&mut target, &json!({"a": {"b": [3], "d": null}, "x": {"y": 2}}), 4).unwrap();
+        assert_eq!(json!({"a": {"b": [3], "c": 1, "d": null}, "x": {"y": 2}}), target);
+
+        let before = target.clone();
+        assert_eq!(Err(DepthLimitError { max_depth: 1 }), merge(&mut target, &json!({"a": {"b": {}}}), 1));
+        assert_eq!(before, target);
+    }
+
+    #[test]
+    fn tracked_merging() {
+        let layers = [
+            ("base".to_owned(), json!({"db": {"host": "x", "port": 1}, "tags": ["a"], "debug": {"level": 1}})),
+            ("local".to_owned(), json!({"db": {"port": 2}, "tags": ["b", "c"], "debug": false, "extra": {"k": null}})),
+        ];
+        let (value, sources) = merge_tracked(&layers, 8).unwrap();
+        assert_eq!(json!({"db": {"host": "x", "port": 2}, "tags": ["b", "c"], "debug": false, "extra": {"k": null}}), value);
+        let mut sources: Vec<(&str, &str)> = sources.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        sources.sort();
+        assert_eq!(vec![
+            ("/db/host", "base"),
+            ("/db/port", "local"),
+            ("/debug", "local"),
+            ("/extra/k", "local"),
+            ("/tags", "local"),
+        ], sources);
+
+        let (value, sources) = merge_tracked(&[], 0).unwrap();
+        assert_eq!(json!({}), value);
+        assert!(sources.is_empty());
+    }
+
+    #[test]
+    fn strings() {
+        let value = json!({
+            "name": "doc",
+            "sections": [
+                {"title": "One", "body": ["a/b", 2, null]},
+                {"title": "Two"},
+            ],
+        });
+        assert_eq!(vec![
+            ("/name".to_owned(), "doc"),
+            ("/sections/0/body/0".to_owned(), "a/b"),
+            ("/sections/0/title".to_owned(), "One"),
+            ("/sections/1/title".to_owned(), "Two"),
+        ], string_leaves(&value));
+        assert_eq!(vec![
+            ("/k".to_owned(), "k"),
+            ("/k/x".to_owned(), "x"),
+            ("/k/x".to_owned(), "v"),
+        ], string_leaves_with_keys(&json!({"k": {"x": "v"}})));
+        assert!(string_leaves(&json!([1, true])).is_empty());
+    }
+
+    #[test]
+    fn deeply_nested() {
+        let mut value = json!(null);
+        for _ in 0..100_000 {
+            value = Value::Array(vec![value]);
+        }
+        assert_eq!(Err(DepthLimitError { max_depth: 1000 }), walk(&value, 1000, |_, _| {}));
+        let mut target = json!({});
+        assert_eq!(Err(DepthLimitError { max_depth: 1000 }), merge(&mut target, &value, 1000));
+        assert_eq!(json!({}), target);
+        let layers = [("base".to_owned(), json!({"a": 1})), ("deep".to_owned(), value)];
+        assert_eq!(Err(DepthLimitError { max_depth: 1000 }), merge_tracked(&layers, 1000));
+        let [_, (_, value)] = layers;
+        // dropping the value recursively would overflow the stack
+        std::mem::forget(value);
+    }
+}
@@ -72,6 +72,8 @@ fn main() {
 pub use serde::{Deserialize, Serialize, Deserializer, Serializer};
 
 pub mod json;
+pub mod yaml;
+pub mod csv;
 
 /// Work with generic deserialization.
 pub mod generic_deserialization {
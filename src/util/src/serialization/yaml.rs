@@ -0,0 +1,139 @@
+/*!
+Working with YAML serialization.
+
+# Example
+
+```
+use rialight_util::serialization::{*, yaml};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+let point: Point = yaml::deserialize("x: 1\ny: 2\n").unwrap();
+assert_eq!(Point { x: 1, y: 2 }, point);
+assert_eq!("x: 1\ny: 2\n", yaml::serialize(&point).unwrap());
+```
+
+# Multiple documents
+
+A YAML string may contain multiple documents separated by `---`.
+Use [`deserialize_yaml_multi`] to deserialize all of them at once,
+or [`deserialize_yaml_multi_iter`] to deserialize them one at a time.
+*/
+
+use super::{Deserialize, Serialize};
+use super::generic_deserialization::DeserializeOwned;
+
+pub use serde_yaml::{
+    Error,
+    Mapping,
+    Number,
+    Value,
+};
+
+/// Result of a YAML operation.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Deserializes a YAML string into a value.
+pub fn deserialize<'a, T>(string: &'a str) -> Result<T>
+    where T: Deserialize<'a>
+{
+    serde_yaml::from_str(string)
+}
+
+/// Deserializes YAML given as a sequence of bytes into a value.
+pub fn deserialize_from_slice<'a, T>(slice: &'a [u8]) -> Result<T>
+    where T: Deserialize<'a>
+{
+    serde_yaml::from_slice(slice)
+}
+
+/// Deserializes YAML from a reader into a value.
+pub fn deserialize_from_reader<R, T>(reader: R) -> Result<T>
+    where
+        R: std::io::Read,
+        T: DeserializeOwned
+{
+    serde_yaml::from_reader(reader)
+}
+
+/// Deserializes every document of a YAML string into a value.
+///
+/// An empty document, such as the one between two consecutive `---`
+/// separators, is deserialized from a null value.
+///
+/// # Example
+///
+/// ```
+/// use rialight_util::serialization::yaml;
+/// let documents: Vec<i32> = yaml::deserialize_yaml_multi("1\n---\n2\n").unwrap();
+/// assert_eq!(vec![1, 2], documents);
+/// ```
+pub fn deserialize_yaml_multi<T>(string: &str) -> Result<Vec<T>>
+    where T: DeserializeOwned
+{
+    deserialize_yaml_multi_iter(string).collect()
+}
+
+/// Returns an iterator that deserializes the documents of a
+/// YAML string one at a time. See [`deserialize_yaml_multi`].
+pub fn deserialize_yaml_multi_iter<'a, T>(string: &'a str) -> impl Iterator<Item = Result<T>> + 'a
+    where T: DeserializeOwned + 'a
+{
+    serde_yaml::Deserializer::from_str(string).map(T::deserialize)
+}
+
+/// Interprets a `Value`, or untyped YAML data, as an instance of type `T`.
+pub fn untyped_to_typed<T>(value: Value) -> Result<T>
+    where T: DeserializeOwned
+{
+    serde_yaml::from_value(value)
+}
+
+/// Converts `T` into untyped YAML data of type `Value`.
+pub fn typed_to_untyped<T>(value: T) -> Result<Value>
+    where T: Serialize
+{
+    serde_yaml::to_value(value)
+}
+
+/// Serializes a value into a YAML string.
+pub fn serialize<T>(value: &T) -> Result<String>
+    where T: ?Sized + Serialize
+{
+    serde_yaml::to_string(value)
+}
+
+/// Serializes a value into YAML using an I/O stream.
+pub fn serialize_with_writer<W, T>(writer: W, value: &T) -> Result<()>
+    where
+        W: std::io::Write,
+        T: ?Sized + Serialize
+{
+    serde_yaml::to_writer(writer, value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn multi() {
+        let documents: Vec<Value> = deserialize_yaml_multi("a: 1\n---\nb: [x, y]\n---\nc: true\n").unwrap();
+        assert_eq!(3, documents.len());
+        assert_eq!(Some(1), documents[0]["a"].as_i64());
+        assert_eq!(Some("y"), documents[1]["b"][1].as_str());
+        assert_eq!(Some(true), documents[2]["c"].as_bool());
+
+        let documents: Vec<Option<i32>> = deserialize_yaml_multi("---\n---\n5\n").unwrap();
+        assert_eq!(vec![None, Some(5)], documents);
+
+        let mut iter = deserialize_yaml_multi_iter::<i32>("1\n---\nx\n");
+        assert_eq!(1, iter.next().unwrap().unwrap());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+}
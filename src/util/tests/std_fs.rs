@@ -0,0 +1,80 @@
+#![cfg(feature = "std-fs")]
+
+use rialight_util::file_paths::os_based::{self, OsPathManipulation};
+
+#[test]
+fn find_up() {
+    let root = std::env::temp_dir().join(format!("rialight_util_find_up_{}", std::process::id()));
+    let deep = root.join("a").join("b");
+    std::fs::create_dir_all(&deep).unwrap();
+    std::fs::write(root.join("marker.toml"), "").unwrap();
+
+    let manipulation = if cfg!(windows) { OsPathManipulation::Windows } else { OsPathManipulation::Common };
+    let start = deep.to_str().unwrap();
+    let found = os_based::find_up(start, "marker.toml", manipulation);
+    let missing = os_based::find_up(start, "missing-marker.toml", manipulation);
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(Some(os_based::resolve_one(root.to_str().unwrap(), manipulation)), found);
+    assert_eq!(None, missing);
+}
+
+#[test]
+fn existing_prefix() {
+    let root = std::env::temp_dir().join(format!("rialight_util_existing_prefix_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("a").join("b")).unwrap();
+
+    let manipulation = if cfg!(windows) { OsPathManipulation::Windows } else { OsPathManipulation::Common };
+    let path = |rel: &str| os_based::resolve(root.to_str().unwrap(), rel, manipulation);
+    let partial = os_based::existing_prefix(&path("a/b/c/d"), manipulation);
+    let complete = os_based::existing_prefix(&path("a/b/"), manipulation);
+    let sibling = os_based::existing_prefix(&path("x/../a/y"), manipulation);
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(path("a/b"), partial);
+    assert_eq!(path("a/b"), complete);
+    assert_eq!(path("a"), sibling);
+}
+
+#[test]
+fn to_absolute() {
+    let root = std::env::temp_dir().join(format!("rialight_util_to_absolute_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    let previous = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&root).unwrap();
+    let cwd = std::env::current_dir().unwrap();
+
+    let manipulation = if cfg!(windows) { OsPathManipulation::Windows } else { OsPathManipulation::Common };
+    let relative = os_based::to_absolute("a/../b/./c", manipulation);
+    let absolute = os_based::to_absolute("/x/../y", manipulation);
+    std::env::set_current_dir(previous).unwrap();
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(os_based::resolve(cwd.to_str().unwrap(), "b/c", manipulation), relative.unwrap());
+    assert_eq!("/y", absolute.unwrap());
+}
+
+#[test]
+fn merge_json_files() {
+    use rialight_util::serialization::json::{self, json, LoadError};
+
+    let root = std::env::temp_dir().join(format!("rialight_util_merge_json_files_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("base.json"), r#"{"server": {"host": "localhost", "port": 80}, "debug": false}"#).unwrap();
+    std::fs::write(root.join("local.json"), r#"{"server": {"port": 8080}, "debug": true}"#).unwrap();
+    std::fs::write(root.join("broken.json"), "{").unwrap();
+
+    let manipulation = if cfg!(windows) { OsPathManipulation::Windows } else { OsPathManipulation::Common };
+    let path = |name: &str| os_based::resolve(root.to_str().unwrap(), name, manipulation);
+    let merged = json::merge_json_files(&[&path("base.json"), &path("local.json")], manipulation);
+    let reversed = json::merge_json_files(&[&path("local.json"), &path("base.json")], manipulation);
+    let broken = json::merge_json_files(&[&path("base.json"), &path("broken.json")], manipulation);
+    let missing = json::merge_json_files(&[&path("missing.json")], manipulation);
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(json!({"server": {"host": "localhost", "port": 8080}, "debug": true}), merged.unwrap());
+    assert_eq!(json!({"server": {"host": "localhost", "port": 80}, "debug": false}), reversed.unwrap());
+    assert!(matches!(broken, Err(LoadError::Json { path: p, .. }) if p == path("broken.json")));
+    assert!(matches!(missing, Err(LoadError::Io { path: p, .. }) if p.ends_with("missing.json")));
+    assert_eq!(json!({}), json::merge_json_files(&[], manipulation).unwrap());
+}